@@ -0,0 +1,718 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use ruzstd::decoding::StreamingDecoder;
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::file::{self, GitIgnore, TreeEntry};
+use crate::template::Origin;
+
+/// Archive formats templates can be packed into, detected from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    TarZst,
+    Zip,
+}
+
+impl ArchiveFormat {
+    const EXTENSIONS: [(&'static str, Self); 6] = [
+        (".tar.gz", Self::TarGz),
+        (".tgz", Self::TarGz),
+        (".tar.zst", Self::TarZst),
+        (".tzst", Self::TarZst),
+        (".tar", Self::Tar),
+        (".zip", Self::Zip),
+    ];
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        Self::EXTENSIONS
+            .iter()
+            .find(|(ext, _)| name.ends_with(ext))
+            .map(|(_, format)| *format)
+    }
+
+    /// Strip the archive extension from a file name, e.g. `rust-cli` from `rust-cli.tar.gz`.
+    pub fn strip_extension(name: &str) -> Option<&str> {
+        Self::EXTENSIONS
+            .iter()
+            .find_map(|(ext, _)| name.strip_suffix(ext))
+    }
+}
+
+/// Pack the template directory at `template_path` into the archive `output`.
+/// Files ignored by the `.gitignore` at the top of the template, the `.git` directory
+/// and the origin file are left out.
+/// Ignore files inside the template tree are packed as they are, since they are meant
+/// for the generated project.
+/// Symbolic links are packed as links, and links pointing outside the template are rejected.
+pub fn pack(template_path: &Path, output: &Path) -> Result<()> {
+    let format = ArchiveFormat::from_path(output)
+        .ok_or_else(|| anyhow!("unsupported archive format: {}", output.display()))?;
+
+    if output.exists() {
+        bail!("output path {} already exists", output.display());
+    }
+
+    // collect the files first so that the output is never packed into itself
+    let entries = file::walk_tree(template_path, &[".git", Origin::FILE], GitIgnore::Root)?;
+    check_symlinks(template_path, &entries)?;
+
+    let file = File::create(output)
+        .with_context(|| format!("failed to create archive: {}", output.display()))?;
+
+    let result = match format {
+        ArchiveFormat::Tar => write_tar(file, template_path, &entries).map(drop),
+        ArchiveFormat::TarGz => {
+            let encoder = GzEncoder::new(file, Compression::default());
+            write_tar(encoder, template_path, &entries)
+                .and_then(|encoder| Ok(encoder.finish().map(drop)?))
+        }
+        ArchiveFormat::TarZst => write_tar(Vec::new(), template_path, &entries).and_then(|tar| {
+            let mut file = file;
+            file.write_all(&compress_to_vec(tar.as_slice(), CompressionLevel::Fastest))?;
+            Ok(())
+        }),
+        ArchiveFormat::Zip => write_zip(file, template_path, &entries),
+    };
+
+    if result.is_err() {
+        let _ = fs::remove_file(output);
+    }
+
+    result.with_context(|| format!("failed to write archive: {}", output.display()))
+}
+
+/// Unpack the archive at `archive` into the new directory `dest`.
+/// Entries escaping `dest`, symbolic links pointing outside `dest`
+/// and entries other than files, directories and symbolic links are rejected.
+pub fn unpack(archive: &Path, dest: &Path) -> Result<()> {
+    let format = ArchiveFormat::from_path(archive)
+        .ok_or_else(|| anyhow!("unsupported archive format: {}", archive.display()))?;
+
+    if dest.exists() {
+        bail!("destination path {} already exists", dest.display());
+    }
+
+    let file = File::open(archive)
+        .with_context(|| format!("failed to open archive: {}", archive.display()))?;
+
+    fs::create_dir_all(dest)
+        .with_context(|| format!("failed to create directory: {}", dest.display()))?;
+
+    let result = match format {
+        ArchiveFormat::Tar => read_tar(file, dest),
+        ArchiveFormat::TarGz => read_tar(GzDecoder::new(file), dest),
+        ArchiveFormat::TarZst => StreamingDecoder::new(file)
+            .map_err(|e| anyhow!("failed to read zstd frame: {e}"))
+            .and_then(|decoder| read_tar(decoder, dest)),
+        ArchiveFormat::Zip => read_zip(file, dest),
+    };
+
+    result.with_context(|| format!("failed to unpack archive: {}", archive.display()))
+}
+
+/// Check that the symbolic links among `entries` point inside `base`.
+fn check_symlinks(base: &Path, entries: &[TreeEntry]) -> Result<()> {
+    let base = base
+        .canonicalize()
+        .with_context(|| format!("failed to resolve path: {}", base.display()))?;
+
+    for entry in entries.iter().filter(|entry| entry.is_symlink) {
+        let path = base.join(&entry.path);
+        let inside = path
+            .canonicalize()
+            .is_ok_and(|target| target.starts_with(&base));
+        if !inside {
+            bail!(
+                "symlink {} points outside the template",
+                entry.path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, base: &Path, entries: &[TreeEntry]) -> Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    builder.mode(tar::HeaderMode::Deterministic);
+    builder.follow_symlinks(false);
+
+    for entry in entries {
+        let src = base.join(&entry.path);
+        if entry.is_dir {
+            builder.append_dir(&entry.path, &src)?;
+        } else {
+            builder.append_path_with_name(&src, &entry.path)?;
+        }
+    }
+
+    Ok(builder.into_inner()?)
+}
+
+fn write_zip(file: File, base: &Path, entries: &[TreeEntry]) -> Result<()> {
+    let mut writer = ZipWriter::new(file);
+
+    for entry in entries {
+        let src = base.join(&entry.path);
+        let name = archive_name(&entry.path)?;
+        let options = SimpleFileOptions::default().unix_permissions(unix_mode(&src)?);
+
+        if entry.is_dir {
+            writer.add_directory(name, options)?;
+        } else if entry.is_symlink {
+            let target = fs::read_link(&src)
+                .with_context(|| format!("failed to read symlink: {}", src.display()))?;
+            writer.add_symlink(name, archive_name(&target)?, options)?;
+        } else {
+            writer.start_file(name, options)?;
+            let mut src = File::open(&src)
+                .with_context(|| format!("failed to open file: {}", src.display()))?;
+            io::copy(&mut src, &mut writer)?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+fn read_tar<R: io::Read>(reader: R, dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    let mut links = Vec::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = safe_relative_path(&entry.path()?)?;
+
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => create_dir(&unpack_path(dest, &path)?)?,
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                let dst = new_unpack_path(dest, &path)?;
+                create_parent_dir(&dst)?;
+                entry
+                    .unpack(&dst)
+                    .with_context(|| format!("failed to unpack file: {}", path.display()))?;
+            }
+            tar::EntryType::Symlink => {
+                let target = entry
+                    .link_name()?
+                    .ok_or_else(|| anyhow!("symlink {} has no target", path.display()))?;
+                links.push((path, target.into_owned()));
+            }
+            tar::EntryType::XGlobalHeader => {}
+            other => bail!("unsupported entry {} ({other:?})", path.display()),
+        }
+    }
+
+    create_symlinks(dest, &links)
+}
+
+fn read_zip(file: File, dest: &Path) -> Result<()> {
+    let mut archive = ZipArchive::new(file)?;
+    let mut links = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let path = safe_relative_path(Path::new(entry.name()))?;
+
+        if entry.is_dir() {
+            create_dir(&unpack_path(dest, &path)?)?;
+        } else if entry.is_symlink() {
+            let mut target = String::new();
+            entry
+                .read_to_string(&mut target)
+                .with_context(|| format!("failed to read symlink: {}", path.display()))?;
+            links.push((path, PathBuf::from(target)));
+        } else if entry.is_file() {
+            let dst = new_unpack_path(dest, &path)?;
+            create_parent_dir(&dst)?;
+
+            let mut file = File::create_new(&dst)
+                .with_context(|| format!("failed to create file: {}", dst.display()))?;
+            io::copy(&mut entry, &mut file)
+                .with_context(|| format!("failed to unpack file: {}", path.display()))?;
+
+            #[cfg(unix)]
+            if let Some(mode) = entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&dst, fs::Permissions::from_mode(mode & 0o777))?;
+            }
+        } else {
+            bail!("unsupported entry {}", path.display());
+        }
+    }
+
+    create_symlinks(dest, &links)
+}
+
+/// Get the destination of an archive entry, refusing to write through symbolic links
+/// unpacked earlier.
+fn unpack_path(dest: &Path, path: &Path) -> Result<PathBuf> {
+    let mut dir = dest.to_path_buf();
+    for component in path.parent().into_iter().flat_map(Path::components) {
+        dir.push(component);
+        if dir.is_symlink() {
+            bail!("unsafe path in archive: {}", path.display());
+        }
+    }
+
+    Ok(dest.join(path))
+}
+
+/// Get the destination of an archive entry that must not exist yet, so that files and links
+/// never replace or write through what an earlier entry unpacked.
+fn new_unpack_path(dest: &Path, path: &Path) -> Result<PathBuf> {
+    let dst = unpack_path(dest, path)?;
+    if dst.symlink_metadata().is_ok() {
+        bail!("unsafe path in archive: {} already exists", path.display());
+    }
+
+    Ok(dst)
+}
+
+/// Create the symbolic links of an archive once all other entries are unpacked,
+/// so that no file is written through them.
+fn create_symlinks(dest: &Path, links: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let links = links
+        .iter()
+        .map(|(path, target)| create_symlink(dest, path, target))
+        .collect::<Result<Vec<_>>>()?;
+
+    check_links(dest, &links)
+}
+
+/// Create the symbolic link `path` in `dest`, whose target must be a relative path
+/// that stays inside `dest`.
+fn create_symlink(dest: &Path, path: &Path, target: &Path) -> Result<PathBuf> {
+    let mut depth = path.components().count() - 1;
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => bail!(
+                "unsafe symlink in archive: {} -> {}",
+                path.display(),
+                target.display()
+            ),
+        }
+    }
+
+    let dst = new_unpack_path(dest, path)?;
+    create_parent_dir(&dst)?;
+
+    symlink(target, &dst)
+        .with_context(|| format!("failed to create symlink: {}", path.display()))?;
+    Ok(dst)
+}
+
+/// Check that the unpacked symbolic links resolve inside `dest`, which their targets alone
+/// cannot tell when they go through other links.
+fn check_links(dest: &Path, links: &[PathBuf]) -> Result<()> {
+    let dest = dest
+        .canonicalize()
+        .with_context(|| format!("failed to resolve path: {}", dest.display()))?;
+
+    for link in links {
+        if !link
+            .canonicalize()
+            .is_ok_and(|target| target.starts_with(&dest))
+        {
+            bail!("symlink {} points outside the archive", link.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that an archive entry path stays inside the destination directory.
+fn safe_relative_path(path: &Path) -> Result<PathBuf> {
+    let mut safe = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => safe.push(part),
+            Component::CurDir => {}
+            _ => bail!("unsafe path in archive: {}", path.display()),
+        }
+    }
+
+    if safe.as_os_str().is_empty() {
+        bail!("empty path in archive");
+    }
+
+    Ok(safe)
+}
+
+fn archive_name(path: &Path) -> Result<String> {
+    let parts = path
+        .components()
+        .map(|c| {
+            c.as_os_str()
+                .to_str()
+                .ok_or_else(|| anyhow!("path not valid UTF-8: {}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(parts.join("/"))
+}
+
+#[cfg(unix)]
+fn unix_mode(path: &Path) -> Result<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata =
+        fs::metadata(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(metadata.permissions().mode() & 0o777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(path: &Path) -> Result<u32> {
+    Ok(if path.is_dir() { 0o755 } else { 0o644 })
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _path: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+fn create_dir(path: &Path) -> Result<()> {
+    fs::create_dir_all(path)
+        .with_context(|| format!("failed to create directory: {}", path.display()))
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    match path.parent() {
+        Some(parent) => create_dir(parent),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::Template;
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup_template() -> TemplateHome {
+        let home = TemplateHome::single("packed", Some(r#"description = "Packed""#));
+        let path = home.dirs()[0].path();
+
+        fs::create_dir_all(path.join("template/dir")).unwrap();
+        fs::write(path.join("template/file.txt"), "Some content").unwrap();
+        fs::write(path.join("template/dir/file.txt"), "Other content").unwrap();
+        fs::write(path.join("template/debug.log"), "ignored").unwrap();
+        fs::write(path.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(path.join(Origin::FILE), "ignored").unwrap();
+        fs::create_dir(path.join(".git")).unwrap();
+        fs::write(path.join(".git/HEAD"), "ignored").unwrap();
+
+        home
+    }
+
+    fn round_trip(extension: &str) {
+        let home = setup_template();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join(format!("packed{extension}"));
+        let dest = temp_dir.path().join("unpacked");
+
+        pack(home.dirs()[0].path(), &archive).unwrap();
+        unpack(&archive, &dest).unwrap();
+
+        let template = Template::load(&dest).unwrap();
+        assert_eq!(template.description(), "Packed");
+        assert_that!(dest.join("template/file.txt"), file("Some content"));
+        assert_that!(dest.join("template/dir/file.txt"), file("Other content"));
+        assert!(!dest.join("template/debug.log").exists());
+        assert!(!dest.join(Origin::FILE).exists());
+        assert!(!dest.join(".git").exists());
+    }
+
+    #[test]
+    fn round_trip_tar() {
+        round_trip(".tar");
+    }
+
+    #[test]
+    fn round_trip_tar_gz() {
+        round_trip(".tar.gz");
+    }
+
+    #[test]
+    fn round_trip_tar_zst() {
+        round_trip(".tar.zst");
+    }
+
+    #[test]
+    fn round_trip_zip() {
+        round_trip(".zip");
+    }
+
+    #[test]
+    fn ignore_files_inside_template_tree() {
+        let home = setup_template();
+        let path = home.dirs()[0].path();
+        fs::write(path.join("template/.gitignore"), ".env\n").unwrap();
+        fs::write(path.join("template/.env"), "KEY=value").unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("packed.tar.gz");
+        let dest = temp_dir.path().join("unpacked");
+        pack(path, &archive).unwrap();
+        unpack(&archive, &dest).unwrap();
+
+        assert_that!(dest.join("template/.gitignore"), file(".env\n"));
+        assert_that!(dest.join("template/.env"), file("KEY=value"));
+    }
+
+    fn round_trip_symlink(extension: &str) {
+        let home = setup_template();
+        let path = home.dirs()[0].path();
+        std::os::unix::fs::symlink("dir/file.txt", path.join("template/link.txt")).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join(format!("packed{extension}"));
+        let dest = temp_dir.path().join("unpacked");
+        pack(path, &archive).unwrap();
+        unpack(&archive, &dest).unwrap();
+
+        let link = dest.join("template/link.txt");
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("dir/file.txt"));
+        assert_that!(link, file("Other content"));
+    }
+
+    #[test]
+    fn round_trip_symlink_tar() {
+        round_trip_symlink(".tar");
+    }
+
+    #[test]
+    fn round_trip_symlink_zip() {
+        round_trip_symlink(".zip");
+    }
+
+    #[test]
+    fn symlink_outside_template() {
+        let home = setup_template();
+        let path = home.dirs()[0].path();
+        let outside = tempfile::NamedTempFile::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), path.join("template/secret")).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        for extension in [".tar", ".zip"] {
+            let archive = temp_dir.path().join(format!("packed{extension}"));
+            assert_that!(
+                pack(path, &archive).map_err(|e| format!("{e:#}")),
+                err(contains_substring("points outside the template"))
+            );
+            assert!(!archive.exists());
+        }
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let home = setup_template();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let result = pack(home.dirs()[0].path(), &temp_dir.path().join("packed.rar"));
+        assert_that!(result, err(anything()));
+    }
+
+    #[test]
+    fn output_exists() {
+        let home = setup_template();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("packed.tar.gz");
+        fs::write(&archive, "some content").unwrap();
+
+        assert_that!(pack(home.dirs()[0].path(), &archive), err(anything()));
+    }
+
+    #[test]
+    fn tar_path_traversal() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("evil.tar");
+
+        // tar::Builder refuses to write `..`, so the header name is set by hand
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.as_gnu_mut().unwrap().name[..10].copy_from_slice(b"../escaped");
+        header.set_size(4);
+        header.set_cksum();
+        builder.append(&header, &b"evil"[..]).unwrap();
+        builder.finish().unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        let result = unpack(&archive, &dest);
+        assert_that!(
+            result.map_err(|e| format!("{e:#}")),
+            err(contains_substring("unsafe path"))
+        );
+        assert!(!temp_dir.path().join("escaped").exists());
+    }
+
+    #[test]
+    fn tar_symlink() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("link.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder
+            .append_link(&mut header, "link", "/etc/passwd")
+            .unwrap();
+        builder.finish().unwrap();
+
+        assert_that!(
+            unpack(&archive, &temp_dir.path().join("dest")),
+            err(anything())
+        );
+    }
+
+    #[test]
+    fn tar_symlink_through_symlink() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("link.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        for (name, target) in [("dir", "."), ("link", "dir/..")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            builder.append_link(&mut header, name, target).unwrap();
+        }
+        builder.finish().unwrap();
+
+        assert_that!(
+            unpack(&archive, &temp_dir.path().join("dest")),
+            err(anything())
+        );
+    }
+
+    #[test]
+    fn tar_file_through_symlink() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("link.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "dir", ".").unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        builder
+            .append_data(&mut header, "dir/file.txt", &b"evil"[..])
+            .unwrap();
+        builder.finish().unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        assert_that!(
+            unpack(&archive, &dest).map_err(|e| format!("{e:#}")),
+            err(contains_substring("unsafe path"))
+        );
+        assert!(!dest.join("file.txt").exists());
+    }
+
+    #[test]
+    fn zip_path_traversal() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("evil.zip");
+
+        let mut writer = ZipWriter::new(File::create(&archive).unwrap());
+        writer
+            .start_file("dir/../../escaped", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"evil").unwrap();
+        writer.finish().unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        let result = unpack(&archive, &dest);
+        assert_that!(
+            result.map_err(|e| format!("{e:#}")),
+            err(contains_substring("unsafe path"))
+        );
+        assert!(!temp_dir.path().join("escaped").exists());
+    }
+
+    #[test]
+    fn zip_file_through_chained_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("evil.zip");
+
+        let mut writer = ZipWriter::new(File::create(&archive).unwrap());
+        let options = SimpleFileOptions::default();
+        writer.add_symlink("a/b", "..", options).unwrap();
+        writer.add_symlink("a/d", "b/../pwned", options).unwrap();
+        writer.start_file("a/./d", options).unwrap();
+        writer.write_all(b"evil").unwrap();
+        writer.finish().unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        assert_that!(
+            unpack(&archive, &dest).map_err(|e| format!("{e:#}")),
+            err(contains_substring("unsafe path"))
+        );
+        assert!(!temp_dir.path().join("pwned").exists());
+    }
+
+    #[test]
+    fn zip_chained_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("evil.zip");
+
+        let mut writer = ZipWriter::new(File::create(&archive).unwrap());
+        let options = SimpleFileOptions::default();
+        writer.add_symlink("a/b", "..", options).unwrap();
+        writer.add_symlink("a/d", "b/../pwned", options).unwrap();
+        writer.finish().unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        assert_that!(
+            unpack(&archive, &dest).map_err(|e| format!("{e:#}")),
+            err(contains_substring("points outside"))
+        );
+    }
+
+    #[gtest]
+    fn format_from_path() {
+        expect_eq!(
+            ArchiveFormat::from_path(Path::new("a/b.tar.gz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        expect_eq!(
+            ArchiveFormat::from_path(Path::new("b.tgz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        expect_eq!(
+            ArchiveFormat::from_path(Path::new("b.tar")),
+            Some(ArchiveFormat::Tar)
+        );
+        expect_eq!(
+            ArchiveFormat::from_path(Path::new("b.tar.zst")),
+            Some(ArchiveFormat::TarZst)
+        );
+        expect_eq!(
+            ArchiveFormat::from_path(Path::new("b.zip")),
+            Some(ArchiveFormat::Zip)
+        );
+        expect_eq!(ArchiveFormat::from_path(Path::new("b.git")), None);
+        expect_eq!(ArchiveFormat::strip_extension("b.tar.gz"), Some("b"));
+    }
+}
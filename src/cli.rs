@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand};
 
 #[derive(Debug, Parser)]
@@ -15,8 +17,53 @@ pub enum Command {
     /// Check if a template is available
     Check(CheckArgs),
 
+    /// Show detailed information about a template
+    Show(ShowArgs),
+
     /// Apply a template in the given directory
     Apply(ApplyArgs),
+
+    /// Print a single rendered file of a template
+    Render(RenderArgs),
+
+    /// Install a template from a git repository or an archive
+    Add(AddArgs),
+
+    /// Update templates installed from a git repository
+    Pull(PullArgs),
+
+    /// Pack a template into an archive
+    Pack(PackArgs),
+
+    /// Search templates in the registry
+    Search(SearchArgs),
+
+    /// Create a new template skeleton
+    New(NewArgs),
+
+    /// Create a new template from an existing project
+    Capture(CaptureArgs),
+
+    /// Remove a template from the template home
+    Rm(RmArgs),
+
+    /// Rename a template in the template home
+    Mv(MvArgs),
+
+    /// Copy a template into the template home to customise it
+    Cp(CpArgs),
+
+    /// Open a template directory in the editor
+    Edit(EditArgs),
+
+    /// Run the test cases of a template against their expected output
+    Test(TestArgs),
+
+    /// Print the JSON Schema of meta.toml
+    Schema,
+
+    /// Run a language server for template authoring over stdio
+    Lsp,
 }
 
 #[derive(Debug, Args)]
@@ -24,6 +71,10 @@ pub struct ListArgs {
     /// Show template information in a table
     #[arg(short, long)]
     pub table: bool,
+
+    /// Show templates as a tree of namespaces
+    #[arg(long, conflicts_with = "table")]
+    pub tree: bool,
 }
 
 #[derive(Debug, Args)]
@@ -31,15 +82,158 @@ pub struct CheckArgs {
     /// The name of the template to check
     /// (leave empty to check all templates)
     pub name: Option<String>,
+
+    /// Report unknown keys in meta.toml as warnings instead of errors
+    #[arg(long)]
+    pub no_strict: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    /// The name of the template to show, or the path of a template directory or archive
+    pub name: String,
 }
 
 #[derive(Debug, Args)]
 pub struct ApplyArgs {
-    /// The name of the template to apply
+    /// The name of the template to apply, optionally with a version requirement
+    /// (e.g. `name@^1`), or the path of a template directory or archive
     pub name: String,
 
     /// The directory to apply the template in
-    pub target: String,
+    /// (snippet templates print to stdout instead)
+    pub target: Option<String>,
+
+    /// Append a snippet template to this file instead of printing it
+    #[arg(long, value_name = "FILE", conflicts_with = "target")]
+    pub append: Option<PathBuf>,
+
+    /// Refuse to apply a template with unknown keys in meta.toml
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// The name of the template, or the path of a template directory or archive
+    pub name: String,
+
+    /// The path of the file inside the template directory
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct AddArgs {
+    /// The git URL or path of the repository,
+    /// the path of a `.tar`, `.tar.gz`, `.tgz`, `.tar.zst`, `.tzst` or `.zip` archive,
+    /// or the name of a template in the registry to install
+    pub source: String,
+
+    /// The name to install the template as
+    /// (defaults to the repository name)
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// The branch or tag to check out
+    #[arg(short, long)]
+    pub rev: Option<String>,
+
+    /// The registry index to look template names up in (a path or `file://` URL)
+    #[arg(long, env = "TEMPRO_REGISTRY")]
+    pub registry: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct PullArgs {
+    /// The name of the template to update
+    /// (leave empty to update all installed templates)
+    pub name: Option<String>,
+
+    /// Overwrite local modifications and non fast-forward changes
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PackArgs {
+    /// The name of the template to pack
+    pub name: String,
+
+    /// The archive to create (`.tar`, `.tar.gz`, `.tgz`, `.tar.zst`, `.tzst` or `.zip`)
+    /// (defaults to `<name>.tar.gz` in the current directory)
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// Text to look for in template names, descriptions and tags
+    /// (leave empty to show all templates)
+    pub query: Option<String>,
+
+    /// The registry index to search (a path or `file://` URL)
+    #[arg(long, env = "TEMPRO_REGISTRY")]
+    pub registry: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// The name of the template to create
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CaptureArgs {
+    /// The project directory to capture
+    pub dir: String,
+
+    /// The name of the template to create
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct RmArgs {
+    /// The name of the template to remove
+    pub name: String,
+
+    /// Do not ask for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct MvArgs {
+    /// The name of the template to rename
+    pub from: String,
+
+    /// The new name of the template
+    pub to: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CpArgs {
+    /// The name of the template to copy
+    pub from: String,
+
+    /// The name of the new template
+    pub to: String,
+}
+
+#[derive(Debug, Args)]
+pub struct EditArgs {
+    /// The name of the template to edit
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct TestArgs {
+    /// The name of the template to test
+    /// (leave empty to test all templates)
+    pub name: Option<String>,
+
+    /// Update the expected output with the rendered output
+    #[arg(long)]
+    pub bless: bool,
 }
 
 #[cfg(test)]
@@ -0,0 +1,378 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use super::check::check_template;
+use crate::archive::{self, ArchiveFormat};
+use crate::cli::AddArgs;
+use crate::file;
+use crate::git;
+use crate::registry::Registry;
+use crate::template::Origin;
+
+pub fn handle_add_command(home: &Path, args: &AddArgs) -> Result<()> {
+    let mut source = args.source.clone();
+    let mut rev = args.rev.clone();
+    let mut registry_name = None;
+
+    if let Some(location) = &args.registry
+        && is_registry_name(&args.source)
+    {
+        let registry = Registry::load(location)?;
+        let entry = registry
+            .find(&args.source)
+            .ok_or_else(|| anyhow!("template not found in registry: {}", args.source))?;
+
+        source = entry.source.clone();
+        rev = rev.or_else(|| entry.rev.clone());
+        registry_name = Some(entry.name.clone());
+    }
+
+    let name = match args.name.as_ref().or(registry_name.as_ref()) {
+        Some(name) => name.clone(),
+        None => default_name(&source)
+            .ok_or_else(|| anyhow!("cannot derive a template name from {source}"))?,
+    };
+    file::validate_template_name(&name)?;
+
+    let source_path = Path::new(&source);
+    let is_archive = source_path.is_file() && ArchiveFormat::from_path(source_path).is_some();
+    if is_archive && rev.is_some() {
+        bail!("a revision can only be given for git sources");
+    }
+
+    let dest = home.join(&name);
+    if dest.exists() {
+        bail!("template already exists: {}", dest.display());
+    }
+    file::validate_namespaces(home, &name)?;
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+
+    let result = if is_archive {
+        install_archive(source_path, &dest)
+    } else {
+        install_git(&source, &dest, rev)
+    };
+
+    if let Err(e) = result {
+        if dest.exists() {
+            fs::remove_dir_all(&dest)
+                .with_context(|| format!("failed to remove directory: {}", dest.display()))?;
+        }
+        file::remove_empty_namespaces(home, &dest)?;
+        return Err(e.context(format!("failed to add template {name}")));
+    }
+
+    Ok(())
+}
+
+fn install_archive(archive: &Path, dest: &Path) -> Result<()> {
+    archive::unpack(archive, dest)?;
+    check_template(dest, true)
+}
+
+fn install_git(source: &str, dest: &Path, rev: Option<String>) -> Result<()> {
+    // local paths are recorded absolute so that the template can be updated from anywhere
+    let source = match Path::new(source).canonicalize() {
+        Ok(path) => path.display().to_string(),
+        Err(_) => source.to_string(),
+    };
+
+    git::clone(&source, dest, rev.as_deref())?;
+    check_template(dest, true)?;
+
+    let origin = Origin {
+        source,
+        rev,
+        commit: git::head_commit(dest)?,
+    };
+    origin.save(dest)?;
+    git::exclude(dest, Origin::FILE)
+}
+
+/// Whether `source` should be looked up in the registry,
+/// rather than taken as a path or a git URL.
+fn is_registry_name(source: &str) -> bool {
+    !Path::new(source).exists()
+        && !source.contains(':')
+        && file::validate_template_name(source).is_ok()
+}
+
+/// Derive a template name from the last component of a git URL, path or archive,
+/// e.g. `rust-cli` from `https://example.com/templates/rust-cli.git` or `rust-cli.tar.gz`.
+fn default_name(source: &str) -> Option<String> {
+    let last = source.trim_end_matches('/').rsplit(['/', ':']).next()?;
+    let name = ArchiveFormat::strip_extension(last)
+        .or_else(|| last.strip_suffix(".git"))
+        .unwrap_or(last);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::Template;
+    use crate::test_utils::prelude::*;
+    use crate::test_utils::{TemplateHome, TemplateRepo};
+
+    fn args(source: &str, name: Option<&str>, rev: Option<&str>) -> AddArgs {
+        AddArgs {
+            source: source.to_string(),
+            name: name.map(Into::into),
+            rev: rev.map(Into::into),
+            registry: None,
+        }
+    }
+
+    #[gtest]
+    fn from_file_url() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+
+        handle_add_command(home.path(), &args(&repo.url(), Some("remote"), None)).unwrap();
+
+        let path = home.path().join("remote");
+        let template = Template::load(&path).unwrap();
+        expect_eq!(template.description(), "Remote template");
+        expect_that!(path.join("template/file.txt"), file("initial content"));
+        expect_that!(
+            Origin::load(&path).unwrap(),
+            some(eq(&Origin {
+                source: repo.url(),
+                rev: None,
+                commit: repo.head(),
+            }))
+        );
+    }
+
+    #[gtest]
+    fn from_bare_repo_at_tag() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+        repo.tag("v1.0");
+        let tagged = repo.head();
+        repo.write("template/file.txt", "changed content");
+        repo.commit("change file");
+
+        let remote = tempfile::tempdir().unwrap();
+        let bare = remote.path().join("rust-cli.git");
+        repo.clone_bare(&bare);
+
+        let bare = bare.to_str().unwrap();
+        handle_add_command(home.path(), &args(bare, None, Some("v1.0"))).unwrap();
+
+        let path = home.path().join("rust-cli");
+        expect_that!(path.join("template/file.txt"), file("initial content"));
+        expect_that!(
+            Origin::load(&path).unwrap(),
+            some(eq(&Origin {
+                source: bare.to_string(),
+                rev: Some("v1.0".to_string()),
+                commit: tagged,
+            }))
+        );
+    }
+
+    #[test]
+    fn nested_name() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+
+        handle_add_command(home.path(), &args(&repo.url(), Some("rust/cli"), None)).unwrap();
+
+        assert_that!(
+            Template::load(&home.path().join("rust/cli")),
+            ok(anything())
+        );
+    }
+
+    #[gtest]
+    fn origin_not_a_local_modification() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+
+        handle_add_command(home.path(), &args(&repo.url(), Some("remote"), None)).unwrap();
+
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(home.path().join("remote"))
+            .args(["status", "--porcelain"])
+            .output()
+            .unwrap();
+        expect_that!(status.stdout, is_empty());
+    }
+
+    #[gtest]
+    fn invalid_template_rejected() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"no description"#);
+
+        let result = handle_add_command(home.path(), &args(&repo.url(), Some("invalid"), None));
+
+        expect_that!(result, err(anything()));
+        expect_that!(home.path().join("invalid").exists(), eq(false));
+    }
+
+    #[gtest]
+    fn unknown_keys_rejected() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new("description = \"Remote template\"\nauthor = \"me\"");
+
+        let result = handle_add_command(home.path(), &args(&repo.url(), Some("remote"), None));
+
+        expect_that!(result, err(anything()));
+        expect_that!(home.path().join("remote").exists(), eq(false));
+    }
+
+    #[gtest]
+    fn namespaces_removed_on_failure() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"no description"#);
+
+        let result = handle_add_command(home.path(), &args(&repo.url(), Some("rust/cli"), None));
+
+        expect_that!(result, err(anything()));
+        expect_that!(home.path().join("rust").exists(), eq(false));
+    }
+
+    #[test]
+    fn namespace_is_a_template() {
+        let home = TemplateHome::single("rust", Some(r#"description = "Local""#));
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+
+        let result = handle_add_command(home.path(), &args(&repo.url(), Some("rust/cli"), None));
+
+        assert_that!(result, err(anything()));
+        assert!(!home.path().join("rust/cli").exists());
+    }
+
+    #[test]
+    fn existing_template_not_clobbered() {
+        let home = TemplateHome::single("remote", Some(r#"description = "Local""#));
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+
+        let result = handle_add_command(home.path(), &args(&repo.url(), Some("remote"), None));
+
+        assert_that!(result, err(anything()));
+        let template = Template::load(&home.path().join("remote")).unwrap();
+        assert_eq!(template.description(), "Local");
+    }
+
+    #[test]
+    fn invalid_source() {
+        let home = TemplateHome::new();
+        let result = handle_add_command(home.path(), &args("/invalid/path/repo", None, None));
+        assert_that!(result, err(anything()));
+    }
+
+    fn pack_template(meta: &str, output: &Path) {
+        let home = TemplateHome::single("packed", Some(meta));
+        let path = home.dirs()[0].path();
+        fs::create_dir(path.join("template")).unwrap();
+        fs::write(path.join("template/file.txt"), "packed content").unwrap();
+        archive::pack(path, output).unwrap();
+    }
+
+    #[gtest]
+    fn from_archive() {
+        let home = TemplateHome::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("packed.zip");
+        pack_template(r#"description = "Packed template""#, &archive);
+
+        handle_add_command(home.path(), &args(archive.to_str().unwrap(), None, None)).unwrap();
+
+        let path = home.path().join("packed");
+        expect_eq!(
+            Template::load(&path).unwrap().description(),
+            "Packed template"
+        );
+        expect_that!(path.join("template/file.txt"), file("packed content"));
+        expect_that!(Origin::load(&path).unwrap(), none());
+    }
+
+    #[gtest]
+    fn invalid_archive_rejected() {
+        let home = TemplateHome::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("packed.tar.gz");
+        pack_template("no description", &archive);
+
+        let result = handle_add_command(home.path(), &args(archive.to_str().unwrap(), None, None));
+
+        expect_that!(result, err(anything()));
+        expect_that!(home.path().join("packed").exists(), eq(false));
+    }
+
+    #[gtest]
+    fn from_registry() {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+        repo.tag("v1.0");
+        repo.write("template/file.txt", "changed content");
+        repo.commit("change file");
+
+        let registry_dir = tempfile::tempdir().unwrap();
+        let index = registry_dir.path().join("index.toml");
+        fs::write(
+            &index,
+            format!(
+                "[[templates]]\n\
+                 name = \"rust/cli\"\n\
+                 source = \"{}\"\n\
+                 rev = \"v1.0\"\n",
+                repo.url()
+            ),
+        )
+        .unwrap();
+
+        let registry = Some(index.to_str().unwrap().to_string());
+
+        let mut add_args = args("rust/cli", None, None);
+        add_args.registry = registry.clone();
+        handle_add_command(home.path(), &add_args).unwrap();
+
+        let path = home.path().join("rust/cli");
+        expect_that!(path.join("template/file.txt"), file("initial content"));
+        expect_that!(
+            Origin::load(&path).unwrap(),
+            some(field!(Origin.rev, some(eq("v1.0"))))
+        );
+
+        let mut add_args = args("missing", None, None);
+        add_args.registry = registry;
+        expect_that!(handle_add_command(home.path(), &add_args), err(anything()));
+    }
+
+    #[gtest]
+    fn test_is_registry_name() {
+        expect_true!(is_registry_name("rust/cli"));
+        expect_false!(is_registry_name("https://example.com/rust-cli.git"));
+        expect_false!(is_registry_name("git@example.com:rust-cli.git"));
+        expect_false!(is_registry_name("/invalid/path"));
+        expect_false!(is_registry_name("./rust-cli.tar.gz"));
+    }
+
+    #[gtest]
+    fn test_default_name() {
+        expect_that!(
+            default_name("https://example.com/a/rust-cli.git"),
+            some(eq("rust-cli"))
+        );
+        expect_that!(
+            default_name("git@example.com:rust-cli.git"),
+            some(eq("rust-cli"))
+        );
+        expect_that!(default_name("/path/to/rust-cli/"), some(eq("rust-cli")));
+        expect_that!(default_name("file:///srv/git/web.git"), some(eq("web")));
+        expect_that!(default_name("./rust-cli.tar.gz"), some(eq("rust-cli")));
+        expect_that!(default_name("rust-cli.zip"), some(eq("rust-cli")));
+        expect_that!(default_name(".git"), none());
+    }
+}
@@ -1,17 +1,104 @@
 use std::env;
-use std::path::Path;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 
 use crate::cli::ApplyArgs;
-use crate::template::Template;
+use crate::config::Config;
+use crate::template::{Kind, Level, TemplateSource};
 
-pub fn handle_apply_command(home: &Path, args: &ApplyArgs) -> Result<()> {
+pub fn handle_apply_command(roots: &[PathBuf], config: &Config, args: &ApplyArgs) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    let mut stderr = io::stderr().lock();
+    apply(&mut stdout, &mut stderr, roots, config, args)
+}
+
+/// Apply a template, printing snippets to `writer` and diagnostics to `stderr`.
+fn apply<W: Write, E: Write>(
+    writer: &mut W,
+    stderr: &mut E,
+    roots: &[PathBuf],
+    config: &Config,
+    args: &ApplyArgs,
+) -> Result<()> {
     let current_dir = env::current_dir()?;
-    let target_dir = current_dir.join(&args.target);
 
-    let template = Template::load(&home.join(&args.name))?;
-    template.apply(&target_dir)
+    let template = resolve_template(roots, config, &args.name)?.load()?;
+    let (errors, warnings): (Vec<_>, Vec<_>) = template
+        .diagnostics(args.strict)
+        .into_iter()
+        .partition(|d| d.level() == Level::Error);
+    if !errors.is_empty() {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        bail!("{}", errors.join("\n"));
+    }
+    for warning in warnings {
+        writeln!(stderr, "{warning}")?;
+    }
+
+    match template.kind() {
+        Kind::Project => {
+            if args.append.is_some() {
+                bail!("--append only works with snippet templates");
+            }
+            let Some(target) = &args.target else {
+                bail!("a target directory is required to apply a project template");
+            };
+            template.apply(&current_dir.join(target))
+        }
+        Kind::Snippet => {
+            if args.target.is_some() {
+                bail!(
+                    "snippet template {} is printed to stdout or appended with --append",
+                    args.name
+                );
+            }
+
+            let content = template.render_snippet()?;
+            match &args.append {
+                Some(path) => append_to_file(&current_dir.join(path), &content),
+                None => {
+                    writer.write_all(&content)?;
+                    writer.flush()?;
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Append `content` to the file at `path`, starting on a new line.
+fn append_to_file(path: &Path, content: &[u8]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open file: {}", path.display()))?;
+
+    let mut last = [b'\n'];
+    if file.metadata()?.len() > 0 {
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)
+            .with_context(|| format!("failed to read file: {}", path.display()))?;
+    }
+
+    if last[0] != b'\n' {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(content)
+        .with_context(|| format!("failed to write file: {}", path.display()))
+}
+
+/// Resolve a template by name or path, falling back to the aliases in `config`.
+pub(crate) fn resolve_template(
+    roots: &[PathBuf],
+    config: &Config,
+    name: &str,
+) -> Result<TemplateSource> {
+    config.resolve(name, |name| TemplateSource::resolve(roots, name))
 }
 
 #[cfg(test)]
@@ -19,10 +106,10 @@ mod tests {
     use super::*;
 
     use std::fs;
-    use std::path::PathBuf;
 
     use tempfile::TempDir;
 
+    use crate::archive;
     use crate::test_utils::prelude::*;
     use crate::test_utils::{TemplateHome, temp_wd};
 
@@ -54,9 +141,11 @@ mod tests {
         temp_wd::with_current_dir(temp_dir.path(), || {
             let args = ApplyArgs {
                 name: "test template".to_string(),
-                target: "target".to_string(),
+                target: Some("target".to_string()),
+                append: None,
+                strict: false,
             };
-            handle_apply_command(home.path(), &args).unwrap();
+            handle_apply_command(&[home.path().to_path_buf()], &Config::default(), &args).unwrap();
         });
 
         assert_that!(target, dir_exist());
@@ -65,4 +154,218 @@ mod tests {
         assert_that!(target.join("dir"), dir_exist());
         expect_that!(target.join("dir/file3"), file("some content3"));
     }
+
+    #[gtest]
+    fn from_archive() {
+        let home = setup_home();
+        let (temp_dir, target) = setup_target();
+        archive::pack(home.dirs()[0].path(), &temp_dir.path().join("test.tar.gz")).unwrap();
+
+        temp_wd::with_current_dir(temp_dir.path(), || {
+            let args = ApplyArgs {
+                name: "./test.tar.gz".to_string(),
+                target: Some("target".to_string()),
+                append: None,
+                strict: false,
+            };
+            handle_apply_command(&[], &Config::default(), &args).unwrap();
+        });
+
+        assert_that!(target, dir_exist());
+        expect_that!(target.join("file1"), file("some content1"));
+        expect_that!(target.join("dir/file3"), file("some content3"));
+    }
+
+    #[gtest]
+    fn from_directory_outside_home() {
+        let home = setup_home();
+        let (temp_dir, target) = setup_target();
+
+        temp_wd::with_current_dir(temp_dir.path(), || {
+            let args = ApplyArgs {
+                name: home.dirs()[0].path().to_str().unwrap().to_string(),
+                target: Some("target".to_string()),
+                append: None,
+                strict: false,
+            };
+            handle_apply_command(&[], &Config::default(), &args).unwrap();
+        });
+
+        expect_that!(target.join("file1"), file("some content1"));
+    }
+
+    #[gtest]
+    fn through_alias() {
+        let home = setup_home();
+        let (temp_dir, target) = setup_target();
+
+        let config: Config = toml::from_str(
+            r#"
+[aliases]
+test = { template = "test template" }
+vars = { template = "test template", set = { db = "postgres" } }
+"#,
+        )
+        .unwrap();
+        let roots = [home.path().to_path_buf()];
+
+        temp_wd::with_current_dir(temp_dir.path(), || {
+            for (name, target) in [("test", "target"), ("vars", "target_vars")] {
+                let args = ApplyArgs {
+                    name: name.to_string(),
+                    target: Some(target.to_string()),
+                    append: None,
+                    strict: false,
+                };
+                handle_apply_command(&roots, &config, &args).unwrap();
+            }
+        });
+
+        expect_that!(target.join("file1"), file("some content1"));
+        expect_that!(
+            temp_dir.path().join("target_vars/file1"),
+            file("some content1")
+        );
+    }
+
+    #[gtest]
+    fn strict() {
+        let home = setup_home();
+        let meta_file = home.dirs()[0].path().join("meta.toml");
+        fs::write(&meta_file, "description = \"Test\"\nauthor = \"me\"\n").unwrap();
+        let (temp_dir, target) = setup_target();
+        let roots = [home.path().to_path_buf()];
+
+        temp_wd::with_current_dir(temp_dir.path(), || {
+            let args = ApplyArgs {
+                name: "test template".to_string(),
+                target: Some("target".to_string()),
+                append: None,
+                strict: true,
+            };
+            let error = handle_apply_command(&roots, &Config::default(), &args).unwrap_err();
+            expect_that!(
+                error.to_string(),
+                starts_with("error: unknown key `author`")
+            );
+            expect_false!(target.exists());
+
+            let args = ApplyArgs {
+                strict: false,
+                ..args
+            };
+            let mut stderr = Vec::new();
+            apply(
+                &mut io::sink(),
+                &mut stderr,
+                &roots,
+                &Config::default(),
+                &args,
+            )
+            .unwrap();
+            expect_that!(
+                String::from_utf8(stderr).unwrap(),
+                starts_with("warning: unknown key `author`")
+            );
+        });
+
+        expect_that!(target.join("file1"), file("some content1"));
+    }
+
+    mod snippet {
+        use super::*;
+
+        fn setup_snippet() -> TemplateHome {
+            let home = TemplateHome::single(
+                "header",
+                Some("description = \"Header\"\nkind = \"snippet\""),
+            );
+            let template_dir = home.dirs()[0].create_template_dir();
+            fs::write(template_dir.join("header"), "// Licensed under MIT\n").unwrap();
+            home
+        }
+
+        fn args(target: Option<&str>, append: Option<&str>) -> ApplyArgs {
+            ApplyArgs {
+                name: "header".to_string(),
+                target: target.map(str::to_string),
+                append: append.map(PathBuf::from),
+                strict: false,
+            }
+        }
+
+        #[gtest]
+        fn append() {
+            let home = setup_snippet();
+            let roots = [home.path().to_path_buf()];
+            let temp_dir = tempfile::tempdir().unwrap();
+            fs::write(temp_dir.path().join("lib.rs"), "mod foo;").unwrap();
+
+            temp_wd::with_current_dir(temp_dir.path(), || {
+                for file in ["lib.rs", "new.rs"] {
+                    let args = args(None, Some(file));
+                    handle_apply_command(&roots, &Config::default(), &args).unwrap();
+                }
+            });
+
+            expect_that!(
+                temp_dir.path().join("lib.rs"),
+                file("mod foo;\n// Licensed under MIT\n")
+            );
+            expect_that!(
+                temp_dir.path().join("new.rs"),
+                file("// Licensed under MIT\n")
+            );
+        }
+
+        #[test]
+        fn stdout() {
+            let home = setup_snippet();
+            let roots = [home.path().to_path_buf()];
+
+            let mut buffer = Vec::new();
+            apply(
+                &mut buffer,
+                &mut io::sink(),
+                &roots,
+                &Config::default(),
+                &args(None, None),
+            )
+            .unwrap();
+            assert_eq!(buffer, b"// Licensed under MIT\n");
+        }
+
+        #[test]
+        fn target_not_allowed() {
+            let home = setup_snippet();
+            let roots = [home.path().to_path_buf()];
+            let temp_dir = tempfile::tempdir().unwrap();
+
+            temp_wd::with_current_dir(temp_dir.path(), || {
+                let args = args(Some("target"), None);
+                let result = handle_apply_command(&roots, &Config::default(), &args);
+                assert_that!(result, err(anything()));
+            });
+            assert!(!temp_dir.path().join("target").exists());
+        }
+
+        #[test]
+        fn project_requires_target() {
+            let home = setup_home();
+            let roots = [home.path().to_path_buf()];
+            let temp_dir = tempfile::tempdir().unwrap();
+
+            temp_wd::with_current_dir(temp_dir.path(), || {
+                for (target, append) in [(None, None), (None, Some("file"))] {
+                    let args = ApplyArgs {
+                        name: "test template".to_string(),
+                        ..args(target, append)
+                    };
+                    let result = handle_apply_command(&roots, &Config::default(), &args);
+                    assert_that!(result, err(anything()));
+                }
+            });
+            assert!(!temp_dir.path().join("file").exists());
+        }
+    }
 }
@@ -0,0 +1,220 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use super::check::check_template;
+use crate::cli::CaptureArgs;
+use crate::file::{self, GitIgnore};
+use crate::template::Template;
+
+/// Directories never worth capturing, on top of `.gitignore` rules.
+const EXCLUDED: [&str; 2] = [".git", "target"];
+
+pub fn handle_capture_command(home: &Path, args: &CaptureArgs) -> Result<()> {
+    file::validate_template_name(&args.name)?;
+
+    let src = env::current_dir()?.join(&args.dir);
+    if !src.is_dir() {
+        bail!("source path {} is not a directory", src.display());
+    }
+
+    let dest = home.join(&args.name);
+    if dest.exists() {
+        bail!("template already exists: {}", dest.display());
+    }
+    file::validate_namespaces(home, &args.name)?;
+
+    let src = src
+        .canonicalize()
+        .with_context(|| format!("failed to resolve path: {}", src.display()))?;
+    if home.canonicalize().is_ok_and(|home| home.starts_with(&src)) {
+        bail!(
+            "template home {} is inside the source path {}",
+            home.display(),
+            src.display()
+        );
+    }
+
+    if let Err(e) = capture(&src, &dest) {
+        if dest.exists() {
+            fs::remove_dir_all(&dest)
+                .with_context(|| format!("failed to remove directory: {}", dest.display()))?;
+        }
+        file::remove_empty_namespaces(home, &dest)?;
+        return Err(e.context(format!("failed to capture template {}", args.name)));
+    }
+
+    Ok(())
+}
+
+fn capture(src: &Path, dest: &Path) -> Result<()> {
+    let template_dir = dest.join(Template::TEMPLATE_DIR);
+    file::copy_tree(src, &template_dir, &EXCLUDED, GitIgnore::Nested)?;
+
+    let meta_file = dest.join(Template::META_FILE);
+    let meta = toml::to_string(&toml::toml! {
+        description = (format!("Captured from {}", src.display()))
+    })?;
+    fs::write(&meta_file, meta)
+        .with_context(|| format!("failed to write meta file: {}", meta_file.display()))?;
+
+    check_template(dest, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+    use crate::test_utils::{TemplateHome, temp_wd};
+
+    fn setup_project() -> tempfile::TempDir {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("acme-billing");
+
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("target/debug")).unwrap();
+        fs::create_dir_all(project.join(".git")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(project.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(project.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(project.join("debug.log"), "log").unwrap();
+        fs::write(project.join("target/debug/app"), "binary").unwrap();
+        fs::write(project.join(".git/HEAD"), "ref").unwrap();
+
+        temp_dir
+    }
+
+    #[gtest]
+    fn it_works() {
+        let home = TemplateHome::new();
+        let project = setup_project();
+
+        temp_wd::with_current_dir(project.path(), || {
+            let args = CaptureArgs {
+                dir: "acme-billing".to_string(),
+                name: "rust/billing".to_string(),
+            };
+            handle_capture_command(home.path(), &args).unwrap();
+        });
+
+        let path = home.path().join("rust/billing");
+        let template = Template::load(&path).unwrap();
+        expect_that!(template.description(), starts_with("Captured from "));
+
+        let template_dir = path.join(Template::TEMPLATE_DIR);
+        expect_that!(template_dir.join("Cargo.toml"), file("[package]"));
+        expect_that!(template_dir.join("src/main.rs"), file("fn main() {}"));
+        expect_that!(template_dir.join(".gitignore"), file("*.log\n"));
+        expect_false!(template_dir.join("debug.log").exists());
+        expect_false!(template_dir.join("target").exists());
+        expect_false!(template_dir.join(".git").exists());
+    }
+
+    #[gtest]
+    fn symlinks_not_followed() {
+        let home = TemplateHome::new();
+        let project = setup_project();
+        let project_dir = project.path().join("acme-billing");
+        std::os::unix::fs::symlink("src/main.rs", project_dir.join("main.rs")).unwrap();
+
+        let args = CaptureArgs {
+            dir: project_dir.display().to_string(),
+            name: "billing".to_string(),
+        };
+        handle_capture_command(home.path(), &args).unwrap();
+
+        let link = home.path().join("billing/template/main.rs");
+        expect_that!(fs::read_link(&link), ok(eq(Path::new("src/main.rs"))));
+    }
+
+    #[test]
+    fn symlink_outside_rejected() {
+        let home = TemplateHome::new();
+        let project = setup_project();
+        let project_dir = project.path().join("acme-billing");
+        fs::write(project.path().join("secret"), "secret").unwrap();
+        std::os::unix::fs::symlink("../secret", project_dir.join("secret")).unwrap();
+
+        let args = CaptureArgs {
+            dir: project_dir.display().to_string(),
+            name: "billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(home.path(), &args), err(anything()));
+        assert!(!home.path().join("billing").exists());
+    }
+
+    #[test]
+    fn namespaces_removed_on_failure() {
+        let home = TemplateHome::new();
+        let project = setup_project();
+        let project_dir = project.path().join("acme-billing");
+        std::os::unix::fs::symlink("/etc", project_dir.join("etc")).unwrap();
+
+        let args = CaptureArgs {
+            dir: project_dir.display().to_string(),
+            name: "acme/billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(home.path(), &args), err(anything()));
+        assert!(!home.path().join("acme").exists());
+    }
+
+    #[test]
+    fn namespace_is_a_template() {
+        let home = TemplateHome::single("acme", Some(r#"description = "Acme""#));
+        let project = setup_project();
+
+        let args = CaptureArgs {
+            dir: project.path().join("acme-billing").display().to_string(),
+            name: "acme/billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(home.path(), &args), err(anything()));
+        assert!(!home.path().join("acme/billing").exists());
+    }
+
+    #[test]
+    fn existing_template_not_clobbered() {
+        let home = TemplateHome::single("billing", Some(r#"description = "Existing""#));
+        let project = setup_project();
+
+        let args = CaptureArgs {
+            dir: project.path().join("acme-billing").display().to_string(),
+            name: "billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(home.path(), &args), err(anything()));
+        let template = Template::load(&home.path().join("billing")).unwrap();
+        assert_eq!(template.description(), "Existing");
+    }
+
+    #[test]
+    fn source_not_a_directory() {
+        let home = TemplateHome::new();
+        let args = CaptureArgs {
+            dir: "/invalid/path".to_string(),
+            name: "billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(home.path(), &args), err(anything()));
+    }
+
+    #[test]
+    fn home_inside_source() {
+        let project = setup_project();
+        let home = project.path().join("acme-billing/templates");
+        fs::create_dir(&home).unwrap();
+
+        let args = CaptureArgs {
+            dir: project.path().join("acme-billing").display().to_string(),
+            name: "billing".to_string(),
+        };
+
+        assert_that!(handle_capture_command(&home, &args), err(anything()));
+        assert!(!home.join("billing").exists());
+    }
+}
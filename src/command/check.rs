@@ -1,20 +1,36 @@
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 
 use crate::cli::CheckArgs;
-use crate::file;
-use crate::template::Template;
-
-pub fn handle_check_command(home: &Path, args: &CheckArgs) -> Result<()> {
-    let names = match &args.name {
-        Some(name) => vec![name.clone()],
-        None => file::get_all_template_names(home)?,
+use crate::config::Config;
+use crate::file::{self, TemplateEntry};
+use crate::template::{Diagnostic, Level, Template};
+
+pub fn handle_check_command(roots: &[PathBuf], config: &Config, args: &CheckArgs) -> Result<()> {
+    let results = match &args.name {
+        Some(name) => {
+            let entry = config.resolve(name, |name| file::find_template(roots, name));
+
+            match entry {
+                Ok(entry) => check_templates(&[entry], !args.no_strict),
+                Err(e) => vec![CheckResult {
+                    name: name.clone(),
+                    location: None,
+                    result: Err(e),
+                    diagnostics: vec![],
+                }],
+            }
+        }
+        None => {
+            let mut results = check_templates(&file::get_all_templates(roots)?, !args.no_strict);
+            results.extend(check_templates_without_meta(roots)?);
+            results.sort_by(|a, b| a.name.cmp(&b.name));
+            results
+        }
     };
 
-    let results = check_templates(home, &names);
-
     #[cfg(not(test))]
     {
         let mut stdout = io::stdout().lock();
@@ -29,10 +45,28 @@ pub fn handle_check_command(home: &Path, args: &CheckArgs) -> Result<()> {
 }
 
 fn print_check_results<W: Write>(writer: &mut W, results: &[CheckResult]) -> io::Result<()> {
-    for CheckResult { name, result } in results.iter() {
+    for CheckResult {
+        name,
+        location,
+        result,
+        diagnostics,
+    } in results.iter()
+    {
+        let name = match location {
+            Some(location) => format!("{name} (shadowed: {})", location.display()),
+            None => name.clone(),
+        };
+
         match result {
             Ok(_) => writeln!(writer, "[Passed] {name}")?,
-            Err(e) => writeln!(writer, "[Failed] {name}: {e}")?,
+            Err(e) => match e.downcast_ref::<Diagnostic>() {
+                Some(diagnostic) => writeln!(writer, "[Failed] {name}\n{diagnostic}")?,
+                None => writeln!(writer, "[Failed] {name}: {e}")?,
+            },
+        }
+
+        for diagnostic in diagnostics {
+            writeln!(writer, "{diagnostic}")?;
         }
     }
 
@@ -42,25 +76,86 @@ fn print_check_results<W: Write>(writer: &mut W, results: &[CheckResult]) -> io:
 #[derive(Debug)]
 struct CheckResult {
     pub name: String,
+    /// Location of a shadowed template, which would otherwise be ambiguous by name.
+    pub location: Option<PathBuf>,
     pub result: Result<()>,
+    /// Problems found on top of `result`, such as unknown keys in meta.toml.
+    pub diagnostics: Vec<Diagnostic>,
 }
 
-fn check_templates(home: &Path, names: &[String]) -> Vec<CheckResult> {
-    names
+fn check_templates(entries: &[TemplateEntry], strict: bool) -> Vec<CheckResult> {
+    entries
         .iter()
-        .map(|name| CheckResult {
-            name: name.clone(),
-            result: Template::load(&home.join(name)).map(|_| ()),
+        .map(|entry| {
+            let (result, diagnostics) = lint_template(&entry.path(), strict);
+            CheckResult {
+                name: entry.name.clone(),
+                location: entry.shadowed.then(|| entry.path()),
+                result,
+                diagnostics,
+            }
         })
         .collect()
 }
 
+/// Report the directories that look like templates but have no meta file,
+/// which would otherwise be skipped as namespaces.
+fn check_templates_without_meta(roots: &[PathBuf]) -> Result<Vec<CheckResult>> {
+    let mut results = Vec::new();
+
+    for root in roots.iter().filter(|root| root.is_dir()) {
+        for name in file::get_template_names_without_meta(root)? {
+            let meta_file = root.join(&name).join(Template::META_FILE);
+            results.push(CheckResult {
+                name,
+                location: None,
+                result: Err(anyhow!("meta file is missing: {}", meta_file.display())),
+                diagnostics: vec![],
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Check the template at `path` and collect its diagnostics (see [`Template::diagnostics`]).
+fn lint_template(path: &Path, strict: bool) -> (Result<()>, Vec<Diagnostic>) {
+    let template = match Template::load(path) {
+        Ok(template) => template,
+        Err(e) => return (Err(e), vec![]),
+    };
+
+    let mut result = template.verify();
+    let diagnostics = template.diagnostics(strict);
+
+    if diagnostics.iter().any(|d| d.level() == Level::Error) {
+        result = result.and(Err(anyhow!("meta.toml has unknown keys")));
+    }
+
+    (result, diagnostics)
+}
+
+/// Check that the template at `path` is valid the way `check` reports it,
+/// including its template tree and, if `strict`, unknown keys in meta.toml.
+pub(crate) fn check_template(path: &Path, strict: bool) -> Result<()> {
+    let (result, diagnostics) = lint_template(path, strict);
+
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.level() == Level::Error)
+        .map(ToString::to_string)
+        .collect();
+    if errors.is_empty() {
+        result
+    } else {
+        result.with_context(|| errors.join("\n"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use anyhow::anyhow;
-
     use crate::test_utils::TemplateHome;
     use crate::test_utils::prelude::*;
 
@@ -71,6 +166,13 @@ mod tests {
         home.push("Template3", Some(r#"no description"#));
         home.push("Template4", None);
         home.push("Template5", Some(r#"description = "Template5 success""#));
+        for dir in home.dirs() {
+            dir.create_template_dir();
+        }
+        home.push(
+            "Template6",
+            Some(r#"description = "Template6 no template dir""#),
+        );
         home
     }
 
@@ -99,16 +201,26 @@ mod tests {
                 "Template3".to_string(),
                 "Template4".to_string(),
                 "Template5".to_string(),
+                "Template6".to_string(),
             ];
 
-            let results = check_templates(home.path(), &names);
-
-            assert_eq!(results.len(), 5);
+            let entries: Vec<_> = names
+                .iter()
+                .map(|name| TemplateEntry {
+                    name: name.clone(),
+                    root: home.path().to_path_buf(),
+                    shadowed: false,
+                })
+                .collect();
+            let results = check_templates(&entries, true);
+
+            assert_eq!(results.len(), 6);
             assert_result(&results, "Template1", true);
             assert_result(&results, "Template2", true);
             assert_result(&results, "Template3", false);
             assert_result(&results, "Template4", false);
             assert_result(&results, "Template5", true);
+            assert_result(&results, "Template6", false);
         }
     }
 
@@ -119,11 +231,21 @@ mod tests {
         let results = vec![
             CheckResult {
                 name: "Template1".to_string(),
+                location: None,
                 result: Ok(()),
+                diagnostics: vec![],
             },
             CheckResult {
                 name: "Template2".to_string(),
+                location: None,
                 result: Err(anyhow!("Failed to load")),
+                diagnostics: vec![],
+            },
+            CheckResult {
+                name: "Template2".to_string(),
+                location: Some(PathBuf::from("/other/Template2")),
+                result: Ok(()),
+                diagnostics: vec![],
             },
         ];
 
@@ -132,8 +254,49 @@ mod tests {
         let output = String::from_utf8(buffer).unwrap();
         assert_eq!(
             output,
-            "[Passed] Template1\n[Failed] Template2: Failed to load\n",
+            "[Passed] Template1\n\
+             [Failed] Template2: Failed to load\n\
+             [Passed] Template2 (shadowed: /other/Template2)\n",
+        );
+    }
+
+    #[test]
+    fn print_diagnostic() {
+        let home = TemplateHome::single("Template1", Some("descripton = \"Test\"\n"));
+        let mut buffer = Vec::new();
+
+        let results = check_templates(
+            &file::get_all_templates(&[home.path().to_path_buf()]).unwrap(),
+            true,
+        );
+        print_check_results(&mut buffer, &results).unwrap();
+
+        let meta_file = home.dirs()[0].path().join(Template::META_FILE);
+        let expected = format!(
+            "[Failed] Template1\n\
+             error: missing field `description`\n \
+             --> {}:1:1\n  \
+             |\n\
+             1 | descripton = \"Test\"\n  \
+             | ^^^^^^^^^^ unknown key\n  \
+             |\n  \
+             = help: did you mean `description`?\n",
+            meta_file.display()
+        );
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[gtest]
+    fn test_check_template() {
+        let home = TemplateHome::single("Test", Some("description = \"Test\"\nauthor = \"me\""));
+        home.dirs()[0].create_template_dir();
+        let path = home.dirs()[0].path();
+
+        expect_that!(
+            check_template(path, true).map_err(|e| format!("{e:#}")),
+            err(contains_substring("error: unknown key `author`"))
         );
+        expect_that!(check_template(path, false), ok(()));
     }
 
     mod test_handle_check_command {
@@ -142,11 +305,14 @@ mod tests {
         #[test]
         fn with_name_passed() {
             let home = TemplateHome::single("Test", Some(r#"description = "Test template""#));
+            home.dirs()[0].create_template_dir();
             let args = CheckArgs {
                 name: Some("Test".to_string()),
+                no_strict: false,
             };
 
-            let result = handle_check_command(home.path(), &args);
+            let result =
+                handle_check_command(&[home.path().to_path_buf()], &Config::default(), &args);
             assert_that!(&result, ok(()));
         }
 
@@ -155,9 +321,11 @@ mod tests {
             let home = TemplateHome::single("Test", Some(r#"no description"#));
             let args = CheckArgs {
                 name: Some("Test".to_string()),
+                no_strict: false,
             };
 
-            let result = handle_check_command(home.path(), &args);
+            let result =
+                handle_check_command(&[home.path().to_path_buf()], &Config::default(), &args);
             assert_that!(result, err(anything()));
         }
 
@@ -166,20 +334,119 @@ mod tests {
             let mut home = TemplateHome::new();
             home.push("Template1", Some(r#"description = "Template1 success""#));
             home.push("Template2", Some(r#"description = "Template2 success""#));
+            for dir in home.dirs() {
+                dir.create_template_dir();
+            }
 
-            let args = CheckArgs { name: None };
+            let args = CheckArgs {
+                name: None,
+                no_strict: false,
+            };
 
-            let result = handle_check_command(home.path(), &args);
+            let result =
+                handle_check_command(&[home.path().to_path_buf()], &Config::default(), &args);
             assert_that!(result, ok(()));
         }
 
         #[test]
         fn without_name_failed() {
             let home = setup();
-            let args = CheckArgs { name: None };
+            let args = CheckArgs {
+                name: None,
+                no_strict: false,
+            };
+
+            let result =
+                handle_check_command(&[home.path().to_path_buf()], &Config::default(), &args);
+            assert_that!(result, err(anything()));
+        }
+
+        #[test]
+        fn with_name_not_found() {
+            let home = TemplateHome::single("Test", Some(r#"description = "Test template""#));
+            let args = CheckArgs {
+                name: Some("Missing".to_string()),
+                no_strict: false,
+            };
+
+            let result =
+                handle_check_command(&[home.path().to_path_buf()], &Config::default(), &args);
+            assert_that!(result, err(anything()));
+        }
+
+        #[test]
+        fn with_alias() {
+            let home = TemplateHome::single("Test", Some(r#"description = "Test template""#));
+            home.dirs()[0].create_template_dir();
+            let config: Config = toml::from_str(
+                r#"
+[aliases]
+alias = { template = "Test" }
+"#,
+            )
+            .unwrap();
+            let args = CheckArgs {
+                name: Some("alias".to_string()),
+                no_strict: false,
+            };
+
+            let result = handle_check_command(&[home.path().to_path_buf()], &config, &args);
+            assert_that!(result, ok(()));
+        }
+
+        #[test]
+        fn without_name_check_all_roots() {
+            let first = TemplateHome::single("Test", Some(r#"description = "Test template""#));
+            let second = TemplateHome::single("Test", Some(r#"no description"#));
+            let args = CheckArgs {
+                name: None,
+                no_strict: false,
+            };
+
+            let roots = [first.path().to_path_buf(), second.path().to_path_buf()];
+            let result = handle_check_command(&roots, &Config::default(), &args);
+            assert_that!(result, err(anything()));
+        }
+
+        #[test]
+        fn without_meta() {
+            let home = TemplateHome::single("Test", None);
+            home.dirs()[0].create_template_dir();
+            let roots = [home.path().to_path_buf()];
+
+            let results = check_templates_without_meta(&roots).unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].name, "Test");
+            assert_that!(results[0].result, err(anything()));
+
+            let args = CheckArgs {
+                name: None,
+                no_strict: false,
+            };
+            let result = handle_check_command(&roots, &Config::default(), &args);
+            assert_that!(result, err(anything()));
+        }
+
+        #[test]
+        fn unknown_keys() {
+            let home =
+                TemplateHome::single("Test", Some("description = \"Test\"\nauthor = \"me\""));
+            home.dirs()[0].create_template_dir();
+            let roots = [home.path().to_path_buf()];
 
-            let result = handle_check_command(home.path(), &args);
+            let args = CheckArgs {
+                name: Some("Test".to_string()),
+                no_strict: false,
+            };
+            let result = handle_check_command(&roots, &Config::default(), &args);
             assert_that!(result, err(anything()));
+
+            let args = CheckArgs {
+                name: Some("Test".to_string()),
+                no_strict: true,
+            };
+            let result = handle_check_command(&roots, &Config::default(), &args);
+            assert_that!(result, ok(()));
         }
     }
 }
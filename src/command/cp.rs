@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use super::check::check_template;
+use crate::cli::CpArgs;
+use crate::file::{self, GitIgnore};
+use crate::template::Origin;
+
+pub fn handle_cp_command(roots: &[PathBuf], home: &Path, args: &CpArgs) -> Result<()> {
+    let src = file::find_template(roots, &args.from)?.path();
+
+    file::validate_template_name(&args.to)?;
+    let dst = home.join(&args.to);
+    if dst.exists() {
+        bail!("template already exists: {}", dst.display());
+    }
+    if dst.starts_with(&src) {
+        bail!("cannot copy template {} into itself", args.from);
+    }
+    file::validate_namespaces(home, &args.to)?;
+
+    if let Err(e) = copy_template(&src, &dst) {
+        if dst.exists() {
+            fs::remove_dir_all(&dst)
+                .with_context(|| format!("failed to remove directory: {}", dst.display()))?;
+        }
+        file::remove_empty_namespaces(home, &dst)?;
+        return Err(e.context(format!("failed to copy template {}", args.from)));
+    }
+
+    Ok(())
+}
+
+/// Copy a template, leaving out its git history and origin so that the copy is a fork.
+/// Everything else is copied verbatim, including files matched by `.gitignore` and symlinks.
+fn copy_template(src: &Path, dst: &Path) -> Result<()> {
+    file::copy_tree(src, dst, &[".git", Origin::FILE], GitIgnore::Off)?;
+    check_template(dst, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::Template;
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn args(from: &str, to: &str) -> CpArgs {
+        CpArgs {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[gtest]
+    fn fork_from_other_root() {
+        let team = TemplateHome::single("service", Some(r#"description = "Service""#));
+        let team_path = team.dirs()[0].path();
+        fs::create_dir_all(team_path.join("template")).unwrap();
+        fs::write(team_path.join("template/file.txt"), "Some content").unwrap();
+        fs::write(team_path.join(Origin::FILE), "source = \"x\"").unwrap();
+        fs::create_dir(team_path.join(".git")).unwrap();
+
+        let home = TemplateHome::new();
+        let roots = [home.path().to_path_buf(), team.path().to_path_buf()];
+
+        handle_cp_command(&roots, home.path(), &args("service", "my/service")).unwrap();
+
+        let path = home.path().join("my/service");
+        expect_eq!(Template::load(&path).unwrap().description(), "Service");
+        expect_that!(path.join("template/file.txt"), file("Some content"));
+        expect_false!(path.join(Origin::FILE).exists());
+        expect_false!(path.join(".git").exists());
+        expect_true!(team_path.join(Origin::FILE).exists());
+    }
+
+    #[gtest]
+    fn verbatim() {
+        let home = TemplateHome::single("service", Some(r#"description = "Service""#));
+        let path = home.dirs()[0].path();
+        fs::create_dir_all(path.join("template")).unwrap();
+        fs::write(path.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(path.join("template/.gitignore"), ".env\n").unwrap();
+        fs::write(path.join("template/.env"), "KEY=value").unwrap();
+        fs::write(path.join("template/debug.log"), "log").unwrap();
+        std::os::unix::fs::symlink(".env", path.join("template/link")).unwrap();
+        let roots = [home.path().to_path_buf()];
+
+        handle_cp_command(&roots, home.path(), &args("service", "fork")).unwrap();
+
+        let fork = home.path().join("fork/template");
+        expect_that!(fork.join(".env"), file("KEY=value"));
+        expect_that!(fork.join("debug.log"), file("log"));
+        expect_that!(fs::read_link(fork.join("link")), ok(eq(Path::new(".env"))));
+    }
+
+    #[test]
+    fn existing_template_not_clobbered() {
+        let mut home = TemplateHome::new();
+        home.push("foo", Some(r#"description = "Foo""#));
+        home.push("bar", Some(r#"description = "Bar""#));
+        let roots = [home.path().to_path_buf()];
+
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("foo", "bar")),
+            err(anything())
+        );
+        let template = Template::load(&home.path().join("bar")).unwrap();
+        assert_eq!(template.description(), "Bar");
+    }
+
+    #[test]
+    fn into_itself() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        let roots = [home.path().to_path_buf()];
+
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("foo", "foo/bar")),
+            err(anything())
+        );
+        assert!(!home.path().join("foo/bar").exists());
+    }
+
+    #[test]
+    fn into_other_template() {
+        let mut home = TemplateHome::new();
+        home.push("foo", Some(r#"description = "Foo""#));
+        home.push("bar", Some(r#"description = "Bar""#));
+        let roots = [home.path().to_path_buf()];
+
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("foo", "bar/foo")),
+            err(anything())
+        );
+        assert!(!home.path().join("bar/foo").exists());
+    }
+
+    #[test]
+    fn namespaces_removed_on_failure() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        let roots = [home.path().to_path_buf()];
+        // a file name longer than any file system allows
+        let to = format!("ns/{}", "a".repeat(300));
+
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("foo", &to)),
+            err(anything())
+        );
+        assert!(!home.path().join("ns").exists());
+    }
+
+    #[test]
+    fn invalid_names() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        let roots = [home.path().to_path_buf()];
+
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("missing", "bar")),
+            err(anything())
+        );
+        assert_that!(
+            handle_cp_command(&roots, home.path(), &args("foo", "../bar")),
+            err(anything())
+        );
+    }
+}
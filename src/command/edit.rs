@@ -0,0 +1,102 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::cli::EditArgs;
+use crate::file;
+
+pub fn handle_edit_command(roots: &[PathBuf], args: &EditArgs) -> Result<()> {
+    let path = file::find_template(roots, &args.name)?.path();
+
+    let editor = get_editor();
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| anyhow!("editor command is empty"))?;
+
+    let status = Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("failed to run editor: {editor}"))?;
+
+    if !status.success() {
+        bail!("editor {editor} exited with {status}");
+    }
+
+    Ok(())
+}
+
+/// Get the editor command from `VISUAL` or `EDITOR`, falling back to `vi`.
+fn get_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use temp_env::with_vars;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    #[test]
+    fn open_template_dir() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        let log = home.path().join("editor.log");
+        let script = home.path().join("editor.sh");
+        fs::write(&script, format!("echo \"$@\" > {}", log.display())).unwrap();
+
+        let editor = format!("sh {}", script.display());
+        with_vars(
+            [("VISUAL", None), ("EDITOR", Some(editor.as_str()))],
+            || {
+                let args = EditArgs {
+                    name: "foo".to_string(),
+                };
+                handle_edit_command(&[home.path().to_path_buf()], &args).unwrap();
+            },
+        );
+
+        let expected = format!("{}\n", home.path().join("foo").display());
+        assert_that!(log, file(expected));
+    }
+
+    #[test]
+    fn editor_failed() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+
+        with_vars([("VISUAL", Some("false")), ("EDITOR", None)], || {
+            let args = EditArgs {
+                name: "foo".to_string(),
+            };
+            let result = handle_edit_command(&[home.path().to_path_buf()], &args);
+            assert_that!(result, err(anything()));
+        });
+    }
+
+    #[test]
+    fn test_get_editor() {
+        with_vars(
+            [("VISUAL", Some("code -w")), ("EDITOR", Some("vim"))],
+            || {
+                assert_eq!(get_editor(), "code -w");
+            },
+        );
+        with_vars([("VISUAL", Some("")), ("EDITOR", Some("vim"))], || {
+            assert_eq!(get_editor(), "vim");
+        });
+        with_vars([("VISUAL", None::<&str>), ("EDITOR", None)], || {
+            assert_eq!(get_editor(), "vi");
+        });
+    }
+}
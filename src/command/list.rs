@@ -1,20 +1,28 @@
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Result;
 
+use super::tree::Tree;
 use crate::cli::ListArgs;
-use crate::file;
+use crate::file::{self, TemplateEntry};
 use crate::template::Template;
 
-pub fn handle_list_command(home: &Path, args: &ListArgs) -> Result<()> {
-    let mut names = file::get_all_template_names(home)?;
-    names.sort();
+pub fn handle_list_command(roots: &[PathBuf], args: &ListArgs) -> Result<()> {
+    let entries = file::get_all_templates(roots)?;
 
     let mut stdout = io::stdout().lock();
 
+    let names: Vec<_> = entries
+        .iter()
+        .filter(|entry| !entry.shadowed)
+        .map(|entry| entry.name.clone())
+        .collect();
+
     if args.table {
-        print_template_table(&mut stdout, home, &names)?;
+        print_template_table(&mut stdout, &entries)?;
+    } else if args.tree {
+        print_template_tree(&mut stdout, &names)?;
     } else {
         print_template_names(&mut stdout, &names)?;
     }
@@ -26,35 +34,73 @@ fn print_template_names<W: Write>(writer: &mut W, names: &[String]) -> io::Resul
     writeln!(writer, "{}", names.join(" "))
 }
 
-fn print_template_table<W: Write>(writer: &mut W, home: &Path, names: &[String]) -> Result<()> {
+fn print_template_tree<W: Write>(writer: &mut W, names: &[String]) -> io::Result<()> {
+    let mut tree = Tree::default();
+    for name in names {
+        tree.insert(name.split('/'), false);
+    }
+
+    tree.print(writer, true)
+}
+
+fn print_template_table<W: Write>(writer: &mut W, entries: &[TemplateEntry]) -> Result<()> {
+    let mut rows = Vec::new();
+
+    for entry in entries {
+        let template = Template::load(&entry.path())?;
+
+        let mut source = entry.root.display().to_string();
+        if entry.shadowed {
+            source.push_str(" (shadowed)");
+        }
+
+        rows.push((
+            entry.name.clone(),
+            template.description().to_string(),
+            source,
+        ));
+    }
+
     let mut max_name_len = "Name".len();
+    let mut max_description_len = "Description".len();
 
-    for name in names {
+    for (name, description, _) in &rows {
         max_name_len = max_name_len.max(name.len());
+        max_description_len = max_description_len.max(description.len());
     }
 
     writeln!(
         writer,
-        "{:<width$} Description",
+        "{:<name_width$} {:<description_width$} Source",
         "Name",
-        width = max_name_len
+        "Description",
+        name_width = max_name_len,
+        description_width = max_description_len
     )?;
 
-    writeln!(writer, "{:-<width$} -----------", "", width = max_name_len)?; // separator
-
-    if names.is_empty() {
+    writeln!(
+        writer,
+        "{:-<name_width$} {:-<description_width$} ------",
+        "",
+        "",
+        name_width = max_name_len,
+        description_width = max_description_len
+    )?; // separator
+
+    if rows.is_empty() {
         writeln!(writer, "(no templates found)")?;
         return Ok(());
     }
 
-    for name in names {
-        let template = Template::load(&home.join(name))?;
+    for (name, description, source) in &rows {
         writeln!(
             writer,
-            "{:<width$} {}",
-            template.name(),
-            template.description(),
-            width = max_name_len
+            "{:<name_width$} {:<description_width$} {}",
+            name,
+            description,
+            source,
+            name_width = max_name_len,
+            description_width = max_description_len
         )?;
     }
 
@@ -102,18 +148,61 @@ mod tests {
         }
     }
 
+    mod test_print_template_tree {
+        use super::*;
+
+        #[test]
+        fn empty_list() {
+            let mut output = Vec::new();
+            print_template_tree(&mut output, &[]).unwrap();
+
+            let output = String::from_utf8(output).unwrap();
+            assert_eq!(output, "");
+        }
+
+        #[test]
+        fn it_works() {
+            let mut home = setup();
+            home.push("rust/cli", Some(r#"description = "Rust CLI""#));
+            home.push("rust/lib", Some(r#"description = "Rust library""#));
+            home.push("rust/web/axum", Some(r#"description = "Axum service""#));
+            home.push("web/vite", Some(r#"description = "Vite app""#));
+
+            let mut names = file::get_all_template_names(home.path()).unwrap();
+            names.sort();
+
+            let mut output = Vec::new();
+            print_template_tree(&mut output, &names).unwrap();
+
+            let output = String::from_utf8(output).unwrap();
+            assert_eq!(
+                output,
+                "bar\n\
+                 baz\n\
+                 foo\n\
+                 rust/\n\
+                 ├── cli\n\
+                 ├── lib\n\
+                 └── web/\n\
+                 \x20   └── axum\n\
+                 web/\n\
+                 └── vite\n"
+            );
+        }
+    }
+
     mod test_print_template_table {
         use super::*;
 
         #[test]
         fn empty_list() {
             let mut output = Vec::new();
-            print_template_table(&mut output, Path::new(""), &[]).unwrap();
+            print_template_table(&mut output, &[]).unwrap();
 
             let output = String::from_utf8(output).unwrap();
             assert_eq!(
                 output,
-                "Name Description\n---- -----------\n(no templates found)\n"
+                "Name Description Source\n---- ----------- ------\n(no templates found)\n"
             );
         }
 
@@ -121,20 +210,22 @@ mod tests {
         fn short_names() {
             let home = setup();
 
-            let mut names = file::get_all_template_names(home.path()).unwrap();
-            names.sort();
+            let entries = file::get_all_templates(&[home.path().to_path_buf()]).unwrap();
 
             let mut output = Vec::new();
-            print_template_table(&mut output, home.path(), &names).unwrap();
+            print_template_table(&mut output, &entries).unwrap();
 
             let output = String::from_utf8(output).unwrap();
+            let source = home.path().display();
             assert_eq!(
                 output,
-                "Name Description\n\
-                 ---- -----------\n\
-                 bar  Bar template\n\
-                 baz  Baz template\n\
-                 foo  Foo template\n"
+                format!(
+                    "Name Description  Source\n\
+                     ---- ------------ ------\n\
+                     bar  Bar template {source}\n\
+                     baz  Baz template {source}\n\
+                     foo  Foo template {source}\n"
+                )
             );
         }
 
@@ -146,21 +237,50 @@ mod tests {
                 Some(r#"description = "Longer template name""#),
             );
 
-            let mut names = file::get_all_template_names(home.path()).unwrap();
-            names.sort();
+            let entries = file::get_all_templates(&[home.path().to_path_buf()]).unwrap();
+
+            let mut output = Vec::new();
+            print_template_table(&mut output, &entries).unwrap();
+
+            let output = String::from_utf8(output).unwrap();
+            let source = home.path().display();
+            assert_eq!(
+                output,
+                format!(
+                    "Name                 Description          Source\n\
+                     -------------------- -------------------- ------\n\
+                     bar                  Bar template         {source}\n\
+                     baz                  Baz template         {source}\n\
+                     foo                  Foo template         {source}\n\
+                     longer_template_name Longer template name {source}\n"
+                )
+            );
+        }
+
+        #[test]
+        fn shadowed_templates() {
+            let first = setup();
+            let second = TemplateHome::single("foo", Some(r#"description = "Other foo""#));
+
+            let roots = [first.path().to_path_buf(), second.path().to_path_buf()];
+            let entries = file::get_all_templates(&roots).unwrap();
 
             let mut output = Vec::new();
-            print_template_table(&mut output, home.path(), &names).unwrap();
+            print_template_table(&mut output, &entries).unwrap();
 
             let output = String::from_utf8(output).unwrap();
+            let first = first.path().display();
+            let second = second.path().display();
             assert_eq!(
                 output,
-                "Name                 Description\n\
-                 -------------------- -----------\n\
-                 bar                  Bar template\n\
-                 baz                  Baz template\n\
-                 foo                  Foo template\n\
-                 longer_template_name Longer template name\n"
+                format!(
+                    "Name Description  Source\n\
+                     ---- ------------ ------\n\
+                     bar  Bar template {first}\n\
+                     baz  Baz template {first}\n\
+                     foo  Foo template {first}\n\
+                     foo  Other foo    {second} (shadowed)\n"
+                )
             );
         }
     }
@@ -0,0 +1,7 @@
+use anyhow::Result;
+
+use crate::lsp;
+
+pub fn handle_lsp_command() -> Result<()> {
+    lsp::run()
+}
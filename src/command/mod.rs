@@ -1,7 +1,38 @@
+mod add;
 mod apply;
+mod capture;
 mod check;
+mod cp;
+mod edit;
 mod list;
+mod lsp;
+mod mv;
+mod new;
+mod pack;
+mod pull;
+mod render;
+mod rm;
+mod schema;
+mod search;
+mod show;
+mod test;
+mod tree;
 
+pub use add::handle_add_command;
 pub use apply::handle_apply_command;
+pub use capture::handle_capture_command;
 pub use check::handle_check_command;
+pub use cp::handle_cp_command;
+pub use edit::handle_edit_command;
 pub use list::handle_list_command;
+pub use lsp::handle_lsp_command;
+pub use mv::handle_mv_command;
+pub use new::handle_new_command;
+pub use pack::handle_pack_command;
+pub use pull::handle_pull_command;
+pub use render::handle_render_command;
+pub use rm::handle_rm_command;
+pub use schema::handle_schema_command;
+pub use search::handle_search_command;
+pub use show::handle_show_command;
+pub use test::handle_test_command;
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use crate::cli::MvArgs;
+use crate::file;
+
+pub fn handle_mv_command(home: &Path, args: &MvArgs) -> Result<()> {
+    let src = file::locate_template(home, &args.from)?;
+
+    file::validate_template_name(&args.to)?;
+    let dst = home.join(&args.to);
+    if dst.exists() {
+        bail!("template already exists: {}", dst.display());
+    }
+    if dst.starts_with(&src) {
+        bail!("cannot move template {} into itself", args.from);
+    }
+    file::validate_namespaces(home, &args.to)?;
+
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+
+    if let Err(e) = fs::rename(&src, &dst) {
+        file::remove_empty_namespaces(home, &dst)?;
+        return Err(e).with_context(|| {
+            format!(
+                "failed to move template from {} to {}",
+                src.display(),
+                dst.display()
+            )
+        });
+    }
+    file::remove_empty_namespaces(home, &src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::Template;
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn args(from: &str, to: &str) -> MvArgs {
+        MvArgs {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[gtest]
+    fn it_works() {
+        let home = TemplateHome::single("rust/cli", Some(r#"description = "Rust CLI""#));
+
+        handle_mv_command(home.path(), &args("rust/cli", "cli/rust")).unwrap();
+
+        let template = Template::load(&home.path().join("cli/rust")).unwrap();
+        expect_eq!(template.description(), "Rust CLI");
+        expect_false!(home.path().join("rust").exists());
+    }
+
+    #[test]
+    fn existing_template_not_clobbered() {
+        let mut home = TemplateHome::new();
+        home.push("foo", Some(r#"description = "Foo""#));
+        home.push("bar", Some(r#"description = "Bar""#));
+
+        assert_that!(
+            handle_mv_command(home.path(), &args("foo", "bar")),
+            err(anything())
+        );
+        let template = Template::load(&home.path().join("bar")).unwrap();
+        assert_eq!(template.description(), "Bar");
+    }
+
+    #[test]
+    fn into_itself() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        assert_that!(
+            handle_mv_command(home.path(), &args("foo", "foo/bar")),
+            err(anything())
+        );
+    }
+
+    #[test]
+    fn into_other_template() {
+        let mut home = TemplateHome::new();
+        home.push("foo", Some(r#"description = "Foo""#));
+        home.push("bar", Some(r#"description = "Bar""#));
+
+        assert_that!(
+            handle_mv_command(home.path(), &args("foo", "bar/foo")),
+            err(anything())
+        );
+        assert!(home.path().join("foo").exists());
+        assert!(!home.path().join("bar/foo").exists());
+    }
+
+    #[test]
+    fn namespaces_removed_on_failure() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        // a file name longer than any file system allows
+        let to = format!("ns/{}", "a".repeat(300));
+
+        assert_that!(
+            handle_mv_command(home.path(), &args("foo", &to)),
+            err(anything())
+        );
+        assert!(home.path().join("foo").exists());
+        assert!(!home.path().join("ns").exists());
+    }
+
+    #[test]
+    fn invalid_names() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        assert_that!(
+            handle_mv_command(home.path(), &args("missing", "bar")),
+            err(anything())
+        );
+        assert_that!(
+            handle_mv_command(home.path(), &args("foo", "../bar")),
+            err(anything())
+        );
+    }
+}
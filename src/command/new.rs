@@ -0,0 +1,159 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use super::check::check_template;
+use crate::cli::NewArgs;
+use crate::file;
+use crate::template::Template;
+
+pub fn handle_new_command(home: &Path, args: &NewArgs) -> Result<()> {
+    file::validate_template_name(&args.name)?;
+
+    let path = home.join(&args.name);
+    if path.exists() {
+        bail!("template already exists: {}", path.display());
+    }
+    file::validate_namespaces(home, &args.name)?;
+
+    if let Err(e) = create_skeleton(&path, &args.name).and_then(|()| check_template(&path, true)) {
+        if path.exists() {
+            fs::remove_dir_all(&path)
+                .with_context(|| format!("failed to remove directory: {}", path.display()))?;
+        }
+        file::remove_empty_namespaces(home, &path)?;
+        return Err(e.context(format!("failed to create template {}", args.name)));
+    }
+
+    Ok(())
+}
+
+fn create_skeleton(path: &Path, name: &str) -> Result<()> {
+    let template_dir = path.join(Template::TEMPLATE_DIR);
+    fs::create_dir_all(&template_dir)
+        .with_context(|| format!("failed to create directory: {}", template_dir.display()))?;
+
+    let meta_file = path.join(Template::META_FILE);
+    fs::write(&meta_file, meta_content(name))
+        .with_context(|| format!("failed to write meta file: {}", meta_file.display()))?;
+
+    let example_file = template_dir.join("README.md");
+    fs::write(&example_file, format!("# Project created from {name}\n"))
+        .with_context(|| format!("failed to write file: {}", example_file.display()))?;
+
+    Ok(())
+}
+
+fn meta_content(name: &str) -> String {
+    let description = toml::Value::from(format!("TODO: describe the {name} template"));
+    // the name also appears in comments, which end at a line break
+    let name = name.escape_debug();
+
+    format!(
+        r#"# Metadata of the `{name}` template.
+#
+# Everything under `{template_dir}/` is copied as-is into the target directory by
+# `tempro apply {name} <target>`.
+
+# The version of this file format.
+schema_version = {schema_version}
+
+# A short summary, shown by `tempro list --table`.
+description = {description}
+
+# An optional semver version. Several versions can live side by side
+# as `{name}@<version>` directories, and `tempro apply {name}@^1` picks
+# the highest matching one.
+# version = "0.1.0"
+
+# Set to "snippet" for a template with a single file, which is printed to stdout
+# or appended to a file with `tempro apply {name} --append <file>`.
+# kind = "project"
+"#,
+        template_dir = Template::TEMPLATE_DIR,
+        schema_version = Template::SCHEMA_VERSION,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    #[gtest]
+    fn it_works() {
+        let home = TemplateHome::new();
+        let args = NewArgs {
+            name: "rust/service".to_string(),
+        };
+
+        handle_new_command(home.path(), &args).unwrap();
+
+        let path = home.path().join("rust/service");
+        let template = Template::load(&path).unwrap();
+        expect_eq!(
+            template.description(),
+            "TODO: describe the rust/service template"
+        );
+        expect_eq!(template.version(), None);
+        expect_that!(
+            path.join("template/README.md"),
+            file("# Project created from rust/service\n")
+        );
+        expect_that!(check_template(&path, true), ok(()));
+    }
+
+    #[test]
+    fn existing_template_not_clobbered() {
+        let home = TemplateHome::single("existing", Some(r#"description = "Existing""#));
+        let args = NewArgs {
+            name: "existing".to_string(),
+        };
+
+        assert_that!(handle_new_command(home.path(), &args), err(anything()));
+        let template = Template::load(&home.path().join("existing")).unwrap();
+        assert_eq!(template.description(), "Existing");
+    }
+
+    #[gtest]
+    fn name_with_special_characters() {
+        let home = TemplateHome::new();
+        let args = NewArgs {
+            name: "say \"hi\"\nthere".to_string(),
+        };
+
+        handle_new_command(home.path(), &args).unwrap();
+
+        let template = Template::load(&home.path().join(&args.name)).unwrap();
+        expect_eq!(
+            template.description(),
+            "TODO: describe the say \"hi\"\nthere template"
+        );
+        expect_that!(template.warnings(), is_empty());
+    }
+
+    #[test]
+    fn removed_on_failure() {
+        let home = TemplateHome::new();
+        // a file name longer than any file system allows
+        let args = NewArgs {
+            name: format!("ns/{}", "a".repeat(300)),
+        };
+
+        assert_that!(handle_new_command(home.path(), &args), err(anything()));
+        assert!(!home.path().join("ns").exists());
+    }
+
+    #[test]
+    fn invalid_name() {
+        let home = TemplateHome::new();
+        let args = NewArgs {
+            name: "../escaped".to_string(),
+        };
+
+        assert_that!(handle_new_command(home.path(), &args), err(anything()));
+    }
+}
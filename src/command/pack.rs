@@ -0,0 +1,70 @@
+use std::env;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::check::check_template;
+use crate::archive;
+use crate::cli::PackArgs;
+use crate::file;
+
+pub fn handle_pack_command(roots: &[PathBuf], args: &PackArgs) -> Result<()> {
+    let entry = file::find_template(roots, &args.name)?;
+    check_template(&entry.path(), true)?;
+
+    let current_dir = env::current_dir()?;
+    let output = match &args.output {
+        Some(output) => current_dir.join(output),
+        None => {
+            let base_name = args.name.rsplit('/').next().unwrap_or(&args.name);
+            current_dir.join(format!("{base_name}.tar.gz"))
+        }
+    };
+
+    archive::pack(&entry.path(), &output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::Template;
+    use crate::test_utils::prelude::*;
+    use crate::test_utils::{TemplateHome, temp_wd};
+
+    #[gtest]
+    fn default_output() {
+        let home = TemplateHome::single("rust/cli", Some(r#"description = "Rust CLI""#));
+        home.dirs()[0].create_template_dir();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        temp_wd::with_current_dir(temp_dir.path(), || {
+            let args = PackArgs {
+                name: "rust/cli".to_string(),
+                output: None,
+            };
+            handle_pack_command(&[home.path().to_path_buf()], &args).unwrap();
+        });
+
+        let archive = temp_dir.path().join("cli.tar.gz");
+        let dest = temp_dir.path().join("unpacked");
+        archive::unpack(&archive, &dest).unwrap();
+        expect_eq!(Template::load(&dest).unwrap().description(), "Rust CLI");
+    }
+
+    #[test]
+    fn invalid_template() {
+        let home = TemplateHome::single("invalid", Some("no description"));
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("invalid.zip");
+
+        let args = PackArgs {
+            name: "invalid".to_string(),
+            output: Some(output.to_str().unwrap().to_string()),
+        };
+
+        let result = handle_pack_command(&[home.path().to_path_buf()], &args);
+        assert_that!(result, err(anything()));
+        assert!(!output.exists());
+    }
+}
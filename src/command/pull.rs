@@ -0,0 +1,285 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use super::check::check_template;
+use crate::cli::PullArgs;
+use crate::file;
+use crate::git;
+use crate::template::Origin;
+
+pub fn handle_pull_command(home: &Path, args: &PullArgs) -> Result<()> {
+    let names = match &args.name {
+        Some(name) => {
+            file::validate_template_name(name)?;
+            vec![name.clone()]
+        }
+        None => file::get_all_template_names(home)?
+            .into_iter()
+            .filter(|name| home.join(name).join(Origin::FILE).exists())
+            .collect(),
+    };
+
+    let results: Vec<_> = names
+        .iter()
+        .map(|name| PullResult {
+            name: name.clone(),
+            result: pull_template(&home.join(name), args.force),
+        })
+        .collect();
+
+    #[cfg(not(test))]
+    {
+        let mut stdout = io::stdout().lock();
+        print_pull_results(&mut stdout, &results)?;
+    }
+
+    if !results.iter().all(|r| r.result.is_ok()) {
+        bail!("Some templates failed to update.");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct PullResult {
+    pub name: String,
+    /// The old and new commit if the template changed.
+    pub result: Result<Option<(String, String)>>,
+}
+
+fn print_pull_results<W: Write>(writer: &mut W, results: &[PullResult]) -> io::Result<()> {
+    for PullResult { name, result } in results.iter() {
+        match result {
+            Ok(Some((old, new))) => {
+                writeln!(writer, "[Updated] {name}: {}..{}", short(old), short(new))?
+            }
+            Ok(None) => writeln!(writer, "[Unchanged] {name}")?,
+            Err(e) => writeln!(writer, "[Failed] {name}: {e}")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+/// Update the template at `path` to the latest commit of its tracked ref.
+/// Returns the old and new commit if the template changed.
+fn pull_template(path: &Path, force: bool) -> Result<Option<(String, String)>> {
+    let Some(mut origin) = Origin::load(path)? else {
+        bail!("template was not installed from a source");
+    };
+
+    if !force && !git::is_clean(path)? {
+        bail!("template has local modifications (use --force to overwrite)");
+    }
+
+    let old = git::head_commit(path)?;
+    let new = git::fetch(
+        path,
+        &origin.source,
+        origin.rev.as_deref().unwrap_or("HEAD"),
+    )?;
+
+    if old == new {
+        return Ok(None);
+    }
+
+    if !force && !git::is_ancestor(path, &old, &new)? {
+        bail!(
+            "cannot fast-forward to {} (use --force to overwrite)",
+            short(&new)
+        );
+    }
+
+    git::reset_hard(path, &new)?;
+
+    if let Err(e) = check_template(path, true) {
+        git::reset_hard(path, &old)?;
+        return Err(e.context(format!("template at {} is invalid", short(&new))));
+    }
+
+    origin.commit = new.clone();
+    origin.save(path)?;
+
+    Ok(Some((old, new)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use anyhow::anyhow;
+
+    use crate::cli::AddArgs;
+    use crate::command::handle_add_command;
+    use crate::template::Template;
+    use crate::test_utils::prelude::*;
+    use crate::test_utils::{TemplateHome, TemplateRepo};
+
+    fn setup(rev: Option<&str>) -> (TemplateHome, TemplateRepo) {
+        let home = TemplateHome::new();
+        let repo = TemplateRepo::new(r#"description = "Remote template""#);
+        repo.tag("v1.0");
+
+        let args = AddArgs {
+            source: repo.url(),
+            name: Some("remote".to_string()),
+            rev: rev.map(Into::into),
+            registry: None,
+        };
+        handle_add_command(home.path(), &args).unwrap();
+
+        (home, repo)
+    }
+
+    fn update(repo: &TemplateRepo, content: &str) {
+        repo.write("template/file.txt", content);
+        repo.commit("update file");
+    }
+
+    #[gtest]
+    fn unchanged() {
+        let (home, _repo) = setup(None);
+        let result = pull_template(&home.path().join("remote"), false);
+        expect_that!(result, ok(none()));
+    }
+
+    #[gtest]
+    fn fast_forward() {
+        let (home, repo) = setup(None);
+        let old = repo.head();
+        update(&repo, "new content");
+
+        let path = home.path().join("remote");
+        let result = pull_template(&path, false).unwrap();
+
+        expect_eq!(result, Some((old, repo.head())));
+        expect_that!(path.join("template/file.txt"), file("new content"));
+        expect_eq!(Origin::load(&path).unwrap().unwrap().commit, repo.head());
+    }
+
+    #[gtest]
+    fn pinned_tag() {
+        let (home, repo) = setup(Some("v1.0"));
+        update(&repo, "new content");
+
+        let path = home.path().join("remote");
+        expect_that!(pull_template(&path, false), ok(none()));
+        expect_that!(path.join("template/file.txt"), file("initial content"));
+
+        repo.tag("v1.1");
+        repo.git(&["tag", "--force", "v1.0"]);
+        expect_that!(pull_template(&path, false), ok(some(anything())));
+        expect_that!(path.join("template/file.txt"), file("new content"));
+    }
+
+    #[gtest]
+    fn local_modifications() {
+        let (home, repo) = setup(None);
+        update(&repo, "new content");
+
+        let path = home.path().join("remote");
+        fs::write(path.join("template/file.txt"), "local content").unwrap();
+
+        expect_that!(pull_template(&path, false), err(anything()));
+        expect_that!(path.join("template/file.txt"), file("local content"));
+
+        expect_that!(pull_template(&path, true), ok(some(anything())));
+        expect_that!(path.join("template/file.txt"), file("new content"));
+    }
+
+    #[gtest]
+    fn not_fast_forward() {
+        let (home, repo) = setup(None);
+        update(&repo, "new content");
+
+        let path = home.path().join("remote");
+        pull_template(&path, false).unwrap();
+
+        repo.git(&["reset", "--quiet", "--hard", "v1.0"]);
+        update(&repo, "rewritten content");
+
+        expect_that!(pull_template(&path, false), err(anything()));
+        expect_that!(path.join("template/file.txt"), file("new content"));
+
+        expect_that!(pull_template(&path, true), ok(some(anything())));
+        expect_that!(path.join("template/file.txt"), file("rewritten content"));
+    }
+
+    #[gtest]
+    fn invalid_update_rolled_back() {
+        let (home, repo) = setup(None);
+        let old = repo.head();
+        repo.write(Template::META_FILE, "no description");
+        repo.commit("break template");
+
+        let path = home.path().join("remote");
+        expect_that!(pull_template(&path, false), err(anything()));
+        expect_eq!(git::head_commit(&path).unwrap(), old);
+    }
+
+    #[test]
+    fn not_installed_from_source() {
+        let home = TemplateHome::single("local", Some(r#"description = "Local""#));
+        let result = pull_template(&home.path().join("local"), false);
+        assert_that!(result, err(anything()));
+    }
+
+    #[test]
+    fn pull_all_installed() {
+        let (mut home, repo) = setup(None);
+        home.push("local", Some(r#"description = "Local""#));
+        update(&repo, "new content");
+
+        let args = PullArgs {
+            name: None,
+            force: false,
+        };
+        handle_pull_command(home.path(), &args).unwrap();
+
+        assert_that!(
+            home.path().join("remote/template/file.txt"),
+            file("new content")
+        );
+    }
+
+    #[test]
+    fn test_print_pull_results() {
+        let mut buffer = Vec::new();
+
+        let results = vec![
+            PullResult {
+                name: "Template1".to_string(),
+                result: Ok(Some((
+                    "0123456789abcdef".to_string(),
+                    "fedcba9876543210".to_string(),
+                ))),
+            },
+            PullResult {
+                name: "Template2".to_string(),
+                result: Ok(None),
+            },
+            PullResult {
+                name: "Template3".to_string(),
+                result: Err(anyhow!("Failed to fetch")),
+            },
+        ];
+
+        print_pull_results(&mut buffer, &results).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            output,
+            "[Updated] Template1: 0123456..fedcba9\n\
+             [Unchanged] Template2\n\
+             [Failed] Template3: Failed to fetch\n",
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::apply::resolve_template;
+use crate::cli::RenderArgs;
+use crate::config::Config;
+
+pub fn handle_render_command(roots: &[PathBuf], config: &Config, args: &RenderArgs) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    render(&mut stdout, roots, config, args)
+}
+
+fn render<W: Write>(
+    writer: &mut W,
+    roots: &[PathBuf],
+    config: &Config,
+    args: &RenderArgs,
+) -> Result<()> {
+    let template = resolve_template(roots, config, &args.name)?.load()?;
+    let content = template.render_file(&args.path)?;
+
+    writer.write_all(&content)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup() -> TemplateHome {
+        let home = TemplateHome::single("license", Some(r#"description = "License""#));
+        let template_dir = home.dirs()[0].create_template_dir();
+        std::fs::write(template_dir.join("LICENSE"), "MIT License\n").unwrap();
+        home
+    }
+
+    fn args(path: &str) -> RenderArgs {
+        RenderArgs {
+            name: "license".to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let home = setup();
+        let roots = [home.path().to_path_buf()];
+
+        let mut buffer = Vec::new();
+        render(&mut buffer, &roots, &Config::default(), &args("LICENSE")).unwrap();
+        assert_eq!(buffer, b"MIT License\n");
+    }
+
+    #[test]
+    fn errors() {
+        let home = setup();
+        let roots = [home.path().to_path_buf()];
+
+        let result = handle_render_command(&roots, &Config::default(), &args("missing"));
+        assert_that!(result, err(anything()));
+    }
+}
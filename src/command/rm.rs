@@ -0,0 +1,89 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use crate::cli::RmArgs;
+use crate::file;
+
+pub fn handle_rm_command(home: &Path, args: &RmArgs) -> Result<()> {
+    let path = file::locate_template(home, &args.name)?;
+
+    if !args.yes {
+        let prompt = format!("Remove template {} at {}?", args.name, path.display());
+        if !confirm(&mut io::stdin().lock(), &mut io::stderr().lock(), &prompt)? {
+            bail!("aborted");
+        }
+    }
+
+    fs::remove_dir_all(&path)
+        .with_context(|| format!("failed to remove directory: {}", path.display()))?;
+    file::remove_empty_namespaces(home, &path)
+}
+
+/// Ask a yes/no question, defaulting to no.
+fn confirm<R: BufRead, W: Write>(reader: &mut R, writer: &mut W, prompt: &str) -> io::Result<bool> {
+    write!(writer, "{prompt} [y/N] ")?;
+    writer.flush()?;
+
+    let mut answer = String::new();
+    reader.read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    #[test]
+    fn it_works() {
+        let mut home = TemplateHome::new();
+        home.push("rust/cli", Some(r#"description = "Rust CLI""#));
+        home.push("other", Some(r#"description = "Other""#));
+
+        let args = RmArgs {
+            name: "rust/cli".to_string(),
+            yes: true,
+        };
+        handle_rm_command(home.path(), &args).unwrap();
+
+        assert!(!home.path().join("rust").exists());
+        assert!(home.path().join("other").exists());
+    }
+
+    #[test]
+    fn not_a_template() {
+        let home = TemplateHome::single("rust/cli", Some(r#"description = "Rust CLI""#));
+
+        for name in ["rust", "missing", "../escaped"] {
+            let args = RmArgs {
+                name: name.to_string(),
+                yes: true,
+            };
+            assert_that!(handle_rm_command(home.path(), &args), err(anything()));
+        }
+        assert!(home.path().join("rust/cli").exists());
+    }
+
+    #[gtest]
+    fn test_confirm() {
+        for (input, expected) in [
+            ("y\n", true),
+            ("YES\n", true),
+            ("n\n", false),
+            ("\n", false),
+            ("", false),
+        ] {
+            let mut output = Vec::new();
+            let result = confirm(&mut input.as_bytes(), &mut output, "Remove?").unwrap();
+
+            expect_eq!(result, expected, "input {input:?}");
+            expect_eq!(String::from_utf8(output).unwrap(), "Remove? [y/N] ");
+        }
+    }
+}
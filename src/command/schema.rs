@@ -0,0 +1,34 @@
+use std::io::{self, Write};
+
+use anyhow::Result;
+
+use crate::template::Template;
+
+pub fn handle_schema_command() -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    print_schema(&mut stdout)
+}
+
+fn print_schema<W: Write>(writer: &mut W) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, &Template::meta_schema())?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    #[gtest]
+    fn test_print_schema() {
+        let mut buffer = Vec::new();
+        print_schema(&mut buffer).unwrap();
+
+        let schema: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        expect_eq!(schema["title"], "tempro template metadata");
+        expect_eq!(schema["type"], "object");
+        expect_eq!(schema["properties"]["description"]["type"], "string");
+    }
+}
@@ -0,0 +1,122 @@
+use std::io::{self, Write};
+
+use anyhow::{Result, anyhow};
+
+use crate::cli::SearchArgs;
+use crate::registry::{Registry, RegistryEntry};
+
+pub fn handle_search_command(args: &SearchArgs) -> Result<()> {
+    let location = args
+        .registry
+        .as_ref()
+        .ok_or_else(|| anyhow!("no registry configured (use --registry or set TEMPRO_REGISTRY)"))?;
+
+    let registry = Registry::load(location)?;
+    let entries = registry.search(args.query.as_deref().unwrap_or_default());
+
+    let mut stdout = io::stdout().lock();
+    print_search_results(&mut stdout, &entries)?;
+
+    Ok(())
+}
+
+fn print_search_results<W: Write>(writer: &mut W, entries: &[&RegistryEntry]) -> io::Result<()> {
+    let mut max_name_len = "Name".len();
+    let mut max_description_len = "Description".len();
+
+    for entry in entries {
+        max_name_len = max_name_len.max(entry.name.len());
+        max_description_len = max_description_len.max(entry.description.len());
+    }
+
+    writeln!(
+        writer,
+        "{:<name_width$} {:<description_width$} Tags",
+        "Name",
+        "Description",
+        name_width = max_name_len,
+        description_width = max_description_len
+    )?;
+
+    writeln!(
+        writer,
+        "{:-<name_width$} {:-<description_width$} ----",
+        "",
+        "",
+        name_width = max_name_len,
+        description_width = max_description_len
+    )?; // separator
+
+    if entries.is_empty() {
+        writeln!(writer, "(no templates found)")?;
+        return Ok(());
+    }
+
+    for entry in entries {
+        let line = format!(
+            "{:<name_width$} {:<description_width$} {}",
+            entry.name,
+            entry.description,
+            entry.tags.join(", "),
+            name_width = max_name_len,
+            description_width = max_description_len
+        );
+        writeln!(writer, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, description: &str, tags: &[&str]) -> RegistryEntry {
+        RegistryEntry {
+            name: name.to_string(),
+            description: description.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            source: String::new(),
+            rev: None,
+        }
+    }
+
+    #[test]
+    fn empty_results() {
+        let mut output = Vec::new();
+        print_search_results(&mut output, &[]).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "Name Description Tags\n---- ----------- ----\n(no templates found)\n"
+        );
+    }
+
+    #[test]
+    fn it_works() {
+        let rust_cli = entry("rust-cli", "Command line application", &["rust", "cli"]);
+        let vite = entry("web/vite", "Vite frontend", &[]);
+
+        let mut output = Vec::new();
+        print_search_results(&mut output, &[&rust_cli, &vite]).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "Name     Description              Tags\n\
+             -------- ------------------------ ----\n\
+             rust-cli Command line application rust, cli\n\
+             web/vite Vite frontend\n"
+        );
+    }
+
+    #[test]
+    fn no_registry() {
+        let args = SearchArgs {
+            query: None,
+            registry: None,
+        };
+        assert!(handle_search_command(&args).is_err());
+    }
+}
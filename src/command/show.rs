@@ -0,0 +1,222 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::apply::resolve_template;
+use super::tree::Tree;
+use crate::cli::ShowArgs;
+use crate::config::Config;
+use crate::file::TreeEntry;
+use crate::template::{Kind, Origin, Template};
+
+/// A long-form description next to meta.toml, shown after the summary.
+const README_FILE: &str = "README.md";
+
+pub fn handle_show_command(roots: &[PathBuf], config: &Config, args: &ShowArgs) -> Result<()> {
+    let template = resolve_template(roots, config, &args.name)?.load()?;
+
+    let mut stdout = io::stdout().lock();
+    print_template_info(&mut stdout, &args.name, &template)
+}
+
+fn print_template_info<W: Write>(writer: &mut W, name: &str, template: &Template) -> Result<()> {
+    let location = template.location();
+
+    writeln!(writer, "Name:        {name}")?;
+    writeln!(writer, "Description: {}", template.description())?;
+    if let Some(version) = template.version() {
+        writeln!(writer, "Version:     {version}")?;
+    }
+    if template.kind() == Kind::Snippet {
+        writeln!(writer, "Kind:        snippet")?;
+    }
+    writeln!(writer, "Location:    {}", location.display())?;
+
+    if let Some(origin) = Origin::load(location)? {
+        let commit = &origin.commit[..origin.commit.len().min(7)];
+        match &origin.rev {
+            Some(rev) => writeln!(writer, "Source:      {} ({rev}, {commit})", origin.source)?,
+            None => writeln!(writer, "Source:      {} ({commit})", origin.source)?,
+        }
+    }
+
+    let template_dir = location.join(Template::TEMPLATE_DIR);
+    if template_dir.is_dir() {
+        let entries = template.entries()?;
+
+        let mut count = 0;
+        let mut size = 0;
+        for entry in entries.iter().filter(|entry| !entry.is_dir) {
+            let path = template_dir.join(&entry.path);
+            let metadata = fs::metadata(&path)
+                .with_context(|| format!("failed to read metadata: {}", path.display()))?;
+            count += 1;
+            size += metadata.len();
+        }
+        writeln!(writer, "Files:       {count} ({})", format_size(size))?;
+
+        writeln!(writer)?;
+        writeln!(writer, "{}/", Template::TEMPLATE_DIR)?;
+        print_file_tree(writer, &entries)?;
+    } else {
+        writeln!(writer, "Files:       (no template directory)")?;
+    }
+
+    let readme = location.join(README_FILE);
+    if readme.is_file() {
+        let content = fs::read_to_string(&readme)
+            .with_context(|| format!("failed to read file: {}", readme.display()))?;
+        writeln!(writer)?;
+        writeln!(writer, "{}", content.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn print_file_tree<W: Write>(writer: &mut W, entries: &[TreeEntry]) -> io::Result<()> {
+    let mut tree = Tree::default();
+    for entry in entries {
+        let components = entry.path.iter().map(|c| c.to_string_lossy().into_owned());
+        tree.insert(components, entry.is_dir);
+    }
+
+    tree.print(writer, false)
+}
+
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if size < 1024 {
+        return format!("{size} B");
+    }
+
+    let mut size = size as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup() -> TemplateHome {
+        let home = TemplateHome::single(
+            "rust/cli",
+            Some("description = \"Rust CLI\"\nversion = \"1.2.0\"\n"),
+        );
+        let dir = &home.dirs()[0];
+
+        let template_dir = dir.create_template_dir();
+        fs::create_dir_all(template_dir.join("src/bin")).unwrap();
+        fs::write(template_dir.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(template_dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(template_dir.join("src/bin/tool.rs"), "").unwrap();
+        fs::write(template_dir.join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir(template_dir.join("target")).unwrap();
+        fs::write(template_dir.join("target/ignored"), "ignored").unwrap();
+
+        fs::write(
+            dir.path().join(README_FILE),
+            "# Rust CLI\n\nA command line app.\n\n",
+        )
+        .unwrap();
+
+        home
+    }
+
+    #[test]
+    fn test_print_template_info() {
+        let home = setup();
+        let location = home.dirs()[0].path();
+        Origin {
+            source: "https://example.com/templates.git".to_string(),
+            rev: Some("main".to_string()),
+            commit: "0123456789abcdef".to_string(),
+        }
+        .save(location)
+        .unwrap();
+        let template = Template::load(location).unwrap();
+
+        let mut buffer = Vec::new();
+        print_template_info(&mut buffer, "rust/cli", &template).unwrap();
+
+        let expected = format!(
+            "\
+Name:        rust/cli
+Description: Rust CLI
+Version:     1.2.0
+Location:    {}
+Source:      https://example.com/templates.git (main, 0123456)
+Files:       5 (38 B)
+
+template/
+├── .gitignore
+├── Cargo.toml
+├── src/
+│   ├── bin/
+│   │   └── tool.rs
+│   └── main.rs
+└── target/
+    └── ignored
+
+# Rust CLI
+
+A command line app.
+",
+            location.display()
+        );
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[gtest]
+    fn minimal_template() {
+        let home = TemplateHome::single("minimal", Some(r#"description = "Minimal""#));
+        let location = home.dirs()[0].path();
+        let template = Template::load(location).unwrap();
+
+        let mut buffer = Vec::new();
+        print_template_info(&mut buffer, "minimal", &template).unwrap();
+
+        let expected = format!(
+            "\
+Name:        minimal
+Description: Minimal
+Location:    {}
+Files:       (no template directory)
+",
+            location.display()
+        );
+        expect_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[gtest]
+    fn test_format_size() {
+        expect_eq!(format_size(0), "0 B");
+        expect_eq!(format_size(1023), "1023 B");
+        expect_eq!(format_size(1536), "1.5 KiB");
+        expect_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn resolve_errors() {
+        let home = setup();
+        let args = ShowArgs {
+            name: "missing".to_string(),
+        };
+        let result = handle_show_command(&[home.path().to_path_buf()], &Config::default(), &args);
+        assert_that!(result, err(anything()));
+    }
+}
@@ -0,0 +1,474 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use similar::TextDiff;
+
+use crate::cli::TestArgs;
+use crate::file::{self, GitIgnore};
+use crate::template::Template;
+
+const ANSWERS_FILE: &str = "answers.toml";
+const EXPECTED_DIR: &str = "expected";
+
+pub fn handle_test_command(roots: &[PathBuf], args: &TestArgs) -> Result<()> {
+    let entries = match &args.name {
+        Some(name) => vec![file::find_template(roots, name)?],
+        None => file::get_all_templates(roots)?
+            .into_iter()
+            .filter(|entry| !entry.shadowed)
+            .collect(),
+    };
+
+    let mut results = Vec::new();
+    for entry in &entries {
+        let path = entry.path();
+        let cases = get_test_cases(&path)?;
+        if cases.is_empty() {
+            if args.name.is_some() {
+                bail!("template {} has no test cases", entry.name);
+            }
+            continue;
+        }
+
+        match Template::load(&path) {
+            Ok(template) => {
+                for case in cases {
+                    let case_dir = path.join(Template::TESTS_DIR).join(&case);
+                    results.push(TestResult {
+                        name: format!("{}/{case}", entry.name),
+                        result: run_case(&template, &case_dir, args.bless),
+                    });
+                }
+            }
+            Err(e) => results.push(TestResult {
+                name: entry.name.clone(),
+                result: Err(e),
+            }),
+        }
+    }
+
+    #[cfg(not(test))]
+    {
+        let mut stdout = io::stdout().lock();
+        print_test_results(&mut stdout, &results)?;
+    }
+
+    if !results
+        .iter()
+        .all(|r| matches!(r.result, Ok(Outcome::Passed | Outcome::Blessed)))
+    {
+        bail!("Some template tests failed.");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct TestResult {
+    /// Name of the test case, prefixed with the template name.
+    pub name: String,
+    pub result: Result<Outcome>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Passed,
+    Blessed,
+    Failed(Vec<FileDiff>),
+}
+
+#[derive(Debug, PartialEq)]
+enum FileDiff {
+    /// In `expected/` but not rendered.
+    Missing(PathBuf),
+    /// Rendered but not in `expected/`.
+    Unexpected(PathBuf),
+    Changed(PathBuf, String),
+}
+
+fn print_test_results<W: Write>(writer: &mut W, results: &[TestResult]) -> io::Result<()> {
+    for TestResult { name, result } in results {
+        match result {
+            Ok(Outcome::Passed) => writeln!(writer, "[Passed] {name}")?,
+            Ok(Outcome::Blessed) => writeln!(writer, "[Blessed] {name}")?,
+            Ok(Outcome::Failed(diffs)) => {
+                writeln!(writer, "[Failed] {name}")?;
+                for diff in diffs {
+                    match diff {
+                        FileDiff::Missing(path) => {
+                            writeln!(writer, "  missing: {}", path.display())?
+                        }
+                        FileDiff::Unexpected(path) => {
+                            writeln!(writer, "  unexpected: {}", path.display())?
+                        }
+                        FileDiff::Changed(path, diff) => {
+                            writeln!(writer, "  changed: {}", path.display())?;
+                            for line in diff.lines() {
+                                writeln!(writer, "    {line}")?;
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => writeln!(writer, "[Failed] {name}: {e}")?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the names of the test cases of the template at `path`, sorted.
+fn get_test_cases(path: &Path) -> Result<Vec<String>> {
+    let tests_dir = path.join(Template::TESTS_DIR);
+    if !tests_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut cases = Vec::new();
+    for entry in tests_dir
+        .read_dir()
+        .with_context(|| format!("failed to read directory: {}", tests_dir.display()))?
+    {
+        let entry = entry.with_context(|| "failed to read a directory entry")?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.path().is_dir() && !name.starts_with('.') {
+            cases.push(name);
+        }
+    }
+    cases.sort();
+
+    Ok(cases)
+}
+
+/// Render `template` for the test case at `case_dir` and compare it with `expected/`,
+/// or replace `expected/` with the rendered output when blessing.
+fn run_case(template: &Template, case_dir: &Path, bless: bool) -> Result<Outcome> {
+    check_answers(&case_dir.join(ANSWERS_FILE))?;
+
+    let temp_dir = tempfile::tempdir().with_context(|| "failed to create temp dir")?;
+    let output = temp_dir.path().join("output");
+    template.apply(&output)?;
+
+    let expected = case_dir.join(EXPECTED_DIR);
+    if bless {
+        if expected.exists() {
+            fs::remove_dir_all(&expected)
+                .with_context(|| format!("failed to remove directory: {}", expected.display()))?;
+        }
+        file::copy_tree(&output, &expected, &[], GitIgnore::Off)?;
+        return Ok(Outcome::Blessed);
+    }
+
+    if !expected.is_dir() {
+        bail!(
+            "expected directory {} is missing, run with --bless to create it",
+            expected.display()
+        );
+    }
+
+    let diffs = diff_trees(&expected, &output)?;
+    if diffs.is_empty() {
+        Ok(Outcome::Passed)
+    } else {
+        Ok(Outcome::Failed(diffs))
+    }
+}
+
+fn check_answers(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read answers file: {}", path.display()))?;
+    let answers: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("failed to parse answers file: {}", path.display()))?;
+    if !answers.is_empty() {
+        bail!(
+            "{} sets variables, but templates do not support variables",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn diff_trees(expected: &Path, actual: &Path) -> Result<Vec<FileDiff>> {
+    let mut entries: BTreeMap<PathBuf, (Option<bool>, Option<bool>)> = BTreeMap::new();
+    for entry in file::walk_tree(expected, &[], GitIgnore::Off)? {
+        entries.entry(entry.path).or_default().0 = Some(entry.is_dir);
+    }
+    for entry in file::walk_tree(actual, &[], GitIgnore::Off)? {
+        entries.entry(entry.path).or_default().1 = Some(entry.is_dir);
+    }
+
+    let mut diffs = Vec::new();
+    for (path, kinds) in entries {
+        match kinds {
+            (Some(_), None) => diffs.push(FileDiff::Missing(path)),
+            (None, Some(_)) => diffs.push(FileDiff::Unexpected(path)),
+            (Some(true), Some(true)) => {}
+            (Some(false), Some(false)) => {
+                if let Some(diff) = diff_files(&expected.join(&path), &actual.join(&path), &path)? {
+                    diffs.push(FileDiff::Changed(path, diff));
+                }
+            }
+            (Some(is_dir), Some(_)) => {
+                let diff = if is_dir {
+                    "expected a directory, found a file"
+                } else {
+                    "expected a file, found a directory"
+                };
+                diffs.push(FileDiff::Changed(path, diff.to_string()));
+            }
+            (None, None) => unreachable!("entries come from one of the trees"),
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// Get a unified diff between two files, or `None` if they are identical.
+fn diff_files(expected: &Path, actual: &Path, name: &Path) -> Result<Option<String>> {
+    let read = |path: &Path| {
+        fs::read(path).with_context(|| format!("failed to read file: {}", path.display()))
+    };
+    let (expected, actual) = (read(expected)?, read(actual)?);
+    if expected == actual {
+        return Ok(None);
+    }
+
+    let (Ok(expected), Ok(actual)) = (String::from_utf8(expected), String::from_utf8(actual))
+    else {
+        return Ok(Some("binary files differ".to_string()));
+    };
+
+    let name = name.display();
+    let diff = TextDiff::from_lines(&expected, &actual)
+        .unified_diff()
+        .header(&format!("expected/{name}"), &format!("actual/{name}"))
+        .to_string();
+
+    Ok(Some(diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::anyhow;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup() -> TemplateHome {
+        let home = TemplateHome::single("rust/cli", Some(r#"description = "Rust CLI""#));
+        let path = home.dirs()[0].path();
+
+        let template_dir = path.join(Template::TEMPLATE_DIR);
+        fs::create_dir_all(template_dir.join("src")).unwrap();
+        fs::write(template_dir.join("README.md"), "# CLI\n").unwrap();
+        fs::write(template_dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let expected = path.join("tests/basic").join(EXPECTED_DIR);
+        fs::create_dir_all(expected.join("src")).unwrap();
+        fs::write(expected.join("README.md"), "# CLI\n").unwrap();
+        fs::write(expected.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        home
+    }
+
+    fn args(name: Option<&str>, bless: bool) -> TestArgs {
+        TestArgs {
+            name: name.map(str::to_string),
+            bless,
+        }
+    }
+
+    fn run(home: &TemplateHome, case: &str, bless: bool) -> anyhow::Result<Outcome> {
+        let path = home.dirs()[0].path();
+        let template = Template::load(path).unwrap();
+        run_case(&template, &path.join(Template::TESTS_DIR).join(case), bless)
+    }
+
+    #[gtest]
+    fn it_works() {
+        let home = setup();
+        let roots = [home.path().to_path_buf()];
+
+        expect_that!(
+            handle_test_command(&roots, &args(Some("rust/cli"), false)),
+            ok(anything())
+        );
+        expect_that!(
+            handle_test_command(&roots, &args(None, false)),
+            ok(anything())
+        );
+    }
+
+    #[gtest]
+    fn detects_differences() {
+        let home = setup();
+        let expected = home.dirs()[0].path().join("tests/basic").join(EXPECTED_DIR);
+        fs::write(expected.join("README.md"), "# Old CLI\n").unwrap();
+        fs::remove_file(expected.join("src/main.rs")).unwrap();
+        fs::write(expected.join("LICENSE"), "MIT\n").unwrap();
+
+        let outcome = run(&home, "basic", false).unwrap();
+
+        let diff = "\
+--- expected/README.md
++++ actual/README.md
+@@ -1 +1 @@
+-# Old CLI
++# CLI
+";
+        expect_eq!(
+            outcome,
+            Outcome::Failed(vec![
+                FileDiff::Missing("LICENSE".into()),
+                FileDiff::Changed("README.md".into(), diff.to_string()),
+                FileDiff::Unexpected("src/main.rs".into()),
+            ])
+        );
+
+        let roots = [home.path().to_path_buf()];
+        expect_that!(
+            handle_test_command(&roots, &args(None, false)),
+            err(anything())
+        );
+    }
+
+    #[gtest]
+    fn bless() {
+        let home = setup();
+        let case_dir = home.dirs()[0].path().join("tests/new");
+        fs::create_dir_all(&case_dir).unwrap();
+
+        expect_that!(run(&home, "new", false), err(anything()));
+        expect_eq!(run(&home, "new", true).unwrap(), Outcome::Blessed);
+        expect_that!(
+            case_dir.join(EXPECTED_DIR).join("src/main.rs"),
+            file("fn main() {}\n")
+        );
+        expect_eq!(run(&home, "new", false).unwrap(), Outcome::Passed);
+    }
+
+    #[gtest]
+    fn bless_replaces_stale_files() {
+        let home = setup();
+        let expected = home.dirs()[0].path().join("tests/basic").join(EXPECTED_DIR);
+        fs::write(expected.join("stale"), "stale").unwrap();
+
+        let roots = [home.path().to_path_buf()];
+        handle_test_command(&roots, &args(Some("rust/cli"), true)).unwrap();
+
+        expect_false!(expected.join("stale").exists());
+        expect_eq!(run(&home, "basic", false).unwrap(), Outcome::Passed);
+    }
+
+    #[gtest]
+    fn ignored_files_compared() {
+        let home = setup();
+        let path = home.dirs()[0].path();
+        fs::write(path.join("template/.gitignore"), ".env\n").unwrap();
+        fs::write(path.join("template/.env"), "KEY=value").unwrap();
+        let expected = path.join("tests/basic").join(EXPECTED_DIR);
+        fs::write(expected.join(".gitignore"), ".env\n").unwrap();
+
+        expect_eq!(
+            run(&home, "basic", false).unwrap(),
+            Outcome::Failed(vec![FileDiff::Unexpected(".env".into())])
+        );
+
+        expect_eq!(run(&home, "basic", true).unwrap(), Outcome::Blessed);
+        expect_that!(expected.join(".env"), file("KEY=value"));
+        expect_eq!(run(&home, "basic", false).unwrap(), Outcome::Passed);
+    }
+
+    #[gtest]
+    fn answers() {
+        let home = setup();
+        let case_dir = home.dirs()[0].path().join("tests/basic");
+
+        fs::write(case_dir.join(ANSWERS_FILE), "").unwrap();
+        expect_eq!(run(&home, "basic", false).unwrap(), Outcome::Passed);
+
+        fs::write(case_dir.join(ANSWERS_FILE), r#"name = "demo""#).unwrap();
+        expect_that!(run(&home, "basic", false), err(anything()));
+    }
+
+    #[test]
+    fn no_test_cases() {
+        let home = TemplateHome::single("foo", Some(r#"description = "Foo""#));
+        let roots = [home.path().to_path_buf()];
+
+        assert_that!(
+            handle_test_command(&roots, &args(Some("foo"), false)),
+            err(anything())
+        );
+        assert_that!(
+            handle_test_command(&roots, &args(None, false)),
+            ok(anything())
+        );
+    }
+
+    #[test]
+    fn test_get_test_cases() {
+        let home = setup();
+        let tests_dir = home.dirs()[0].path().join(Template::TESTS_DIR);
+        fs::create_dir(tests_dir.join("another")).unwrap();
+        fs::create_dir(tests_dir.join(".hidden")).unwrap();
+        fs::write(tests_dir.join("notes.txt"), "").unwrap();
+
+        let cases = get_test_cases(home.dirs()[0].path()).unwrap();
+
+        assert_eq!(cases, ["another", "basic"]);
+    }
+
+    #[test]
+    fn test_print_test_results() {
+        let mut buffer = Vec::new();
+
+        let results = vec![
+            TestResult {
+                name: "foo/basic".to_string(),
+                result: Ok(Outcome::Passed),
+            },
+            TestResult {
+                name: "foo/new".to_string(),
+                result: Ok(Outcome::Blessed),
+            },
+            TestResult {
+                name: "bar/basic".to_string(),
+                result: Ok(Outcome::Failed(vec![
+                    FileDiff::Missing("LICENSE".into()),
+                    FileDiff::Unexpected("extra".into()),
+                    FileDiff::Changed("README.md".into(), "-old\n+new\n".to_string()),
+                ])),
+            },
+            TestResult {
+                name: "baz".to_string(),
+                result: Err(anyhow!("Failed to load")),
+            },
+        ];
+
+        print_test_results(&mut buffer, &results).unwrap();
+
+        let expected = "\
+[Passed] foo/basic
+[Blessed] foo/new
+[Failed] bar/basic
+  missing: LICENSE
+  unexpected: extra
+  changed: README.md
+    -old
+    +new
+[Failed] baz: Failed to load
+";
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+}
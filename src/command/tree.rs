@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// A tree of path components, printed like the output of the `tree` command.
+#[derive(Debug, Default)]
+pub(super) struct Tree {
+    is_dir: bool,
+    children: BTreeMap<String, Tree>,
+}
+
+impl Tree {
+    /// Insert the path made of `components`, marking its last node as a directory if `is_dir`.
+    pub(super) fn insert<I, S>(&mut self, components: I, is_dir: bool)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut node = self;
+        for component in components {
+            node = node.children.entry(component.into()).or_default();
+        }
+        node.is_dir |= is_dir;
+    }
+
+    /// Print the tree, drawing branches to the top-level nodes unless `flat_top_level`.
+    pub(super) fn print<W: Write>(&self, writer: &mut W, flat_top_level: bool) -> io::Result<()> {
+        self.print_children(writer, "", flat_top_level)
+    }
+
+    fn print_children<W: Write>(&self, writer: &mut W, prefix: &str, flat: bool) -> io::Result<()> {
+        for (i, (name, child)) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+
+            let (branch, indent) = match (flat, last) {
+                (true, _) => ("", ""),
+                (false, false) => ("├── ", "│   "),
+                (false, true) => ("└── ", "    "),
+            };
+
+            // directories and namespaces are marked with a trailing slash
+            let suffix = if child.is_dir || !child.children.is_empty() {
+                "/"
+            } else {
+                ""
+            };
+
+            writeln!(writer, "{prefix}{branch}{name}{suffix}")?;
+            child.print_children(writer, &format!("{prefix}{indent}"), false)?;
+        }
+
+        Ok(())
+    }
+}
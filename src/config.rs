@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// User configuration, read from `config.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Alias>,
+}
+
+/// A shortcut name for a template, e.g. `svc = { template = "rust/service" }`.
+///
+/// Templates have no variables yet, so the variables an alias presets with
+/// `set = { db = "postgres" }` are ignored with a warning when the alias is used.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Alias {
+    pub template: String,
+    #[serde(default)]
+    pub set: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    /// Load the configuration file at `path`.
+    /// A missing file yields the default configuration.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse config file: {}", path.display()))
+    }
+
+    pub fn alias(&self, name: &str) -> Option<&Alias> {
+        self.aliases.get(name)
+    }
+
+    /// Resolve `name` with `resolve`, falling back to the template of the alias `name`
+    /// when `name` itself cannot be resolved.
+    pub fn resolve<T>(&self, name: &str, resolve: impl Fn(&str) -> Result<T>) -> Result<T> {
+        resolve(name).or_else(|e| {
+            let Some(alias) = self.alias(name) else {
+                return Err(e);
+            };
+            if !alias.set.is_empty() {
+                eprintln!(
+                    "warning: ignoring the variables set by alias {name}, \
+                     templates do not support variables yet"
+                );
+            }
+            resolve(&alias.template)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    #[test]
+    fn missing_file() {
+        let config = Config::load(Path::new("/invalid/path/config.toml")).unwrap();
+        assert_that!(config.aliases, is_empty());
+    }
+
+    #[gtest]
+    fn aliases() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            r#"
+[aliases]
+svc = { template = "rust/service" }
+cli = { template = "rust/cli" }
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+
+        expect_eq!(
+            config.alias("svc"),
+            Some(&Alias {
+                template: "rust/service".to_string(),
+                set: BTreeMap::new(),
+            })
+        );
+        expect_eq!(
+            config.alias("cli"),
+            Some(&Alias {
+                template: "rust/cli".to_string(),
+                set: BTreeMap::new(),
+            })
+        );
+        expect_eq!(config.alias("missing"), None);
+    }
+
+    #[test]
+    fn invalid_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "[aliases]\nsvc = 1").unwrap();
+
+        assert_that!(Config::load(&path), err(anything()));
+    }
+
+    #[gtest]
+    fn alias_with_variables() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[aliases]\nsvc = { template = \"rust/service\", set = { db = \"postgres\" } }",
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+
+        expect_eq!(
+            config.alias("svc"),
+            Some(&Alias {
+                template: "rust/service".to_string(),
+                set: BTreeMap::from([("db".to_string(), "postgres".into())]),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_alias_keys() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[aliases]\nsvc = { template = \"rust/service\", tags = [] }",
+        )
+        .unwrap();
+
+        assert_that!(Config::load(&path), err(anything()));
+    }
+}
@@ -1,7 +1,14 @@
+use std::collections::HashSet;
 use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
+use ignore::WalkBuilder;
+use ignore::gitignore::GitignoreBuilder;
+use semver::{Version, VersionReq};
+
+use crate::template::Template;
 
 /// Get the path to the template home.
 /// First, it checks if the `TEMPRO_HOME` environment variable is set.
@@ -22,33 +29,506 @@ pub fn get_template_home() -> Result<PathBuf> {
     Ok(base.join("tempro"))
 }
 
+/// Get the path to the configuration file.
+/// It is `TEMPRO_CONFIG` if set, or `config.toml` in the template home.
+/// It *does not* check if the file exists.
+pub fn get_config_file() -> Result<PathBuf> {
+    if let Ok(path) = env::var("TEMPRO_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+
+    Ok(get_template_home()?.join("config.toml"))
+}
+
+/// Get the template search path, in order of precedence.
+/// It consists of the entries of `TEMPRO_PATH` (colon-separated),
+/// the project template directories of the current directory (see [`get_project_template_dirs`]),
+/// the template home and `tempro` under each of `XDG_DATA_DIRS`
+/// (default `/usr/local/share:/usr/share`).
+/// Duplicated and empty entries are dropped.
+/// It *does not* check if the paths exist.
+pub fn get_template_path() -> Result<Vec<PathBuf>> {
+    let mut roots = Vec::new();
+
+    if let Some(paths) = env::var_os("TEMPRO_PATH") {
+        roots.extend(env::split_paths(&paths));
+    }
+
+    if let Ok(current_dir) = env::current_dir() {
+        roots.extend(get_project_template_dirs(&current_dir));
+    }
+
+    roots.push(get_template_home()?);
+
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    roots.extend(env::split_paths(&data_dirs).map(|dir| dir.join("tempro")));
+
+    let mut seen = HashSet::new();
+    roots.retain(|root| !root.as_os_str().is_empty() && seen.insert(root.clone()));
+
+    Ok(roots)
+}
+
+/// The project template directory, relative to a project root.
+pub const PROJECT_TEMPLATE_DIR: &str = ".tempro/templates";
+
+/// Get the project template directories of `dir`,
+/// found by walking up from `dir` to the filesystem root, nearest first.
+/// Only existing directories are returned.
+pub fn get_project_template_dirs(dir: &Path) -> Vec<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join(PROJECT_TEMPLATE_DIR))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
 /// Get all template names in the template home.
-/// Only directories are considered.
+/// A directory containing a meta file is a template,
+/// and any other directory is a namespace which is searched recursively.
+/// Names of nested templates are joined with `/`, e.g. `rust/cli`.
+/// Hidden directories are skipped.
 /// It *does not* check if the template is valid.
 pub fn get_all_template_names(home: &Path) -> Result<Vec<String>> {
     let mut names = Vec::new();
+    collect_template_names(home, "", &mut names, &mut Vec::new())?;
+    Ok(names)
+}
+
+/// Get the names of the directories in the template home which have a template directory
+/// but no meta file, so that they are neither templates nor namespaces.
+pub fn get_template_names_without_meta(home: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    collect_template_names(home, "", &mut Vec::new(), &mut names)?;
+    Ok(names)
+}
 
-    for entry in home
+fn collect_template_names(
+    dir: &Path,
+    namespace: &str,
+    names: &mut Vec<String>,
+    without_meta: &mut Vec<String>,
+) -> Result<()> {
+    for entry in dir
         .read_dir()
-        .with_context(|| format!("failed to read template home: {}", home.display()))?
+        .with_context(|| format!("failed to read template home: {}", dir.display()))?
     {
         let entry = entry.with_context(|| "failed to read a directory entry")?;
-        if entry.path().is_dir() {
-            let os_name = entry.file_name();
-            let name = os_name
-                .into_string()
-                .map_err(|os| anyhow!("template name not valid UTF-8: {:?}", os))?;
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let os_name = entry.file_name();
+        let name = os_name
+            .into_string()
+            .map_err(|os| anyhow!("template name not valid UTF-8: {:?}", os))?;
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let name = format!("{namespace}{name}");
+        if path.join(Template::META_FILE).exists() {
             names.push(name);
+        } else if path.join(Template::TEMPLATE_DIR).is_dir() {
+            without_meta.push(name);
+        } else {
+            collect_template_names(&path, &format!("{name}/"), names, without_meta)?;
         }
     }
 
-    Ok(names)
+    Ok(())
+}
+
+/// Check that a template name is made of `/`-separated path components
+/// which are neither empty nor hidden, so that it stays inside the template root.
+pub fn validate_template_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("template name is empty");
+    }
+
+    for component in name.split('/') {
+        if component.is_empty() || component.starts_with('.') || component.contains('\\') {
+            bail!("invalid template name: {name}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the namespaces of `name` in `root` are directories or do not exist yet,
+/// so that they can be created, and that none of them is a template.
+pub fn validate_namespaces(root: &Path, name: &str) -> Result<()> {
+    let Some((namespaces, _)) = name.rsplit_once('/') else {
+        return Ok(());
+    };
+
+    let mut dir = root.to_path_buf();
+    for namespace in namespaces.split('/') {
+        dir.push(namespace);
+        if !dir.exists() {
+            break;
+        }
+        if !dir.is_dir() {
+            bail!("namespace {} is not a directory", dir.display());
+        }
+        if dir.join(Template::META_FILE).exists() {
+            bail!("namespace {} is a template", dir.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// A template directory found in one of the template roots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateEntry {
+    pub name: String,
+    pub root: PathBuf,
+    /// Whether a template with the same name exists in a root of higher precedence.
+    pub shadowed: bool,
+}
+
+impl TemplateEntry {
+    pub fn path(&self) -> PathBuf {
+        self.root.join(&self.name)
+    }
+}
+
+/// Get all templates in the given roots, sorted by name.
+/// Templates with the same name are kept in the order of the roots,
+/// and all but the first one are marked as shadowed.
+/// Roots that do not exist are skipped.
+pub fn get_all_templates(roots: &[PathBuf]) -> Result<Vec<TemplateEntry>> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    for root in roots.iter().filter(|root| root.is_dir()) {
+        for name in get_all_template_names(root)? {
+            entries.push(TemplateEntry {
+                shadowed: !seen.insert(name.clone()),
+                name,
+                root: root.clone(),
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Find the template with the given name.
+///
+/// Several versions of a template can live side by side as `name@version` directories,
+/// versioned by the `version` key of their meta file (or else by the directory suffix).
+/// A name of the form `name@req` selects the highest version matching the semver requirement,
+/// unless a directory with exactly that name exists.
+/// A plain name selects the highest version of `name` and `name@*`,
+/// with unversioned templates ranking lowest.
+/// Versions are only compared within the first root having any version of the template,
+/// so that roots of lower precedence never take over a template.
+pub fn find_template(roots: &[PathBuf], name: &str) -> Result<TemplateEntry> {
+    match lookup_template(roots, name)? {
+        Some(entry) => Ok(entry),
+        None => bail!("template not found: {name}"),
+    }
+}
+
+/// Like [`find_template`], but yields `None` instead of an error
+/// when no root has a template with the given name.
+pub fn lookup_template(roots: &[PathBuf], name: &str) -> Result<Option<TemplateEntry>> {
+    validate_template_name(name)?;
+
+    let (base, req) = match name.rsplit_once('@') {
+        Some((base, req)) => (base, Some(req)),
+        None => (name, None),
+    };
+
+    for root in roots {
+        let candidates = get_version_candidates(root, base)?;
+        if candidates.is_empty() {
+            continue;
+        }
+
+        let name = select_version(root, name, candidates, req)?;
+        return Ok(Some(TemplateEntry {
+            name,
+            root: root.clone(),
+            shadowed: false,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Select the candidate of `name` in `root` as described in [`find_template`].
+fn select_version(
+    root: &Path,
+    name: &str,
+    candidates: Vec<String>,
+    req: Option<&str>,
+) -> Result<String> {
+    let req = match req {
+        Some(_) if candidates.iter().any(|candidate| candidate == name) => {
+            return Ok(name.to_string());
+        }
+        Some(req) => Some(
+            VersionReq::parse(req)
+                .with_context(|| format!("invalid version requirement: {req}"))?,
+        ),
+        None => None,
+    };
+
+    let mut best: Option<(Option<Version>, String)> = None;
+
+    for candidate in candidates {
+        let version = get_template_version(&root.join(&candidate));
+
+        if let Some(req) = &req
+            && !version.as_ref().is_some_and(|version| req.matches(version))
+        {
+            continue;
+        }
+
+        if best.as_ref().is_none_or(|(best, _)| version > *best) {
+            best = Some((version, candidate));
+        }
+    }
+
+    match best {
+        Some((_, candidate)) => Ok(candidate),
+        None => bail!("template not found: {name} in {}", root.display()),
+    }
+}
+
+/// Get the path of the template named exactly `name` in `root`, without version resolution.
+pub fn locate_template(root: &Path, name: &str) -> Result<PathBuf> {
+    validate_template_name(name)?;
+
+    let path = root.join(name);
+    if !path.join(Template::META_FILE).exists() {
+        bail!("template not found: {name}");
+    }
+
+    Ok(path)
+}
+
+/// Remove the empty namespace directories between `path` and `root`.
+pub fn remove_empty_namespaces(root: &Path, path: &Path) -> Result<()> {
+    for dir in path.ancestors().skip(1) {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+
+        let is_empty = dir
+            .read_dir()
+            .with_context(|| format!("failed to read directory: {}", dir.display()))?
+            .next()
+            .is_none();
+        if !is_empty {
+            break;
+        }
+
+        fs::remove_dir(dir)
+            .with_context(|| format!("failed to remove directory: {}", dir.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Get the names of `base` and all `base@*` templates in a root, in that order.
+fn get_version_candidates(root: &Path, base: &str) -> Result<Vec<String>> {
+    let mut candidates = Vec::new();
+
+    if root.join(base).join(Template::META_FILE).exists() {
+        candidates.push(base.to_string());
+    }
+
+    let (namespace, file_name) = match base.rsplit_once('/') {
+        Some((namespace, file_name)) => (format!("{namespace}/"), file_name),
+        None => (String::new(), base),
+    };
+
+    let dir = root.join(&namespace);
+    if !dir.is_dir() {
+        return Ok(candidates);
+    }
+
+    let mut versioned = Vec::new();
+
+    for entry in dir
+        .read_dir()
+        .with_context(|| format!("failed to read directory: {}", dir.display()))?
+    {
+        let entry = entry.with_context(|| "failed to read a directory entry")?;
+        let Ok(entry_name) = entry.file_name().into_string() else {
+            continue;
+        };
+
+        if entry_name
+            .strip_prefix(file_name)
+            .is_some_and(|rest| rest.starts_with('@'))
+            && entry.path().join(Template::META_FILE).exists()
+        {
+            versioned.push(format!("{namespace}{entry_name}"));
+        }
+    }
+
+    versioned.sort();
+    candidates.extend(versioned);
+    Ok(candidates)
+}
+
+/// Get the version of the template at `path`, from its meta file or else its `@version` suffix.
+fn get_template_version(path: &Path) -> Option<Version> {
+    if let Some(version) = Template::load(path).ok()?.version() {
+        return Some(version.clone());
+    }
+
+    let suffix = path.file_name()?.to_str()?.rsplit_once('@')?.1;
+    let padded = match suffix.matches('.').count() {
+        0 => format!("{suffix}.0.0"),
+        1 => format!("{suffix}.0"),
+        _ => suffix.to_string(),
+    };
+    Version::parse(&padded).ok()
+}
+
+/// A file, directory or symbolic link in a tree, relative to the root of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+}
+
+/// Which `.gitignore` files leave entries out of a tree walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitIgnore {
+    /// No ignore files are read, every entry is listed.
+    Off,
+    /// Only the `.gitignore` at the root of the tree, applied to the whole tree.
+    Root,
+    /// Every `.gitignore` in the tree, applied to its own directory as in a git checkout.
+    Nested,
+}
+
+/// List the files and directories under `root`, parents first and sorted by name.
+/// Entries named as in `excluded` and entries ignored by the `.gitignore` files
+/// selected by `gitignore` are left out.
+/// Symbolic links are listed as they are, not followed.
+pub fn walk_tree(root: &Path, excluded: &[&str], gitignore: GitIgnore) -> Result<Vec<TreeEntry>> {
+    let excluded: Vec<_> = excluded.iter().map(|name| name.to_string()).collect();
+
+    let root_ignore = match gitignore {
+        GitIgnore::Root => {
+            let mut builder = GitignoreBuilder::new(root);
+            if let Some(e) = builder.add(root.join(".gitignore")) {
+                // a missing file simply has no rules
+                if !e.is_io() {
+                    return Err(e).with_context(|| "failed to read .gitignore");
+                }
+            }
+            Some(
+                builder
+                    .build()
+                    .with_context(|| "failed to read .gitignore")?,
+            )
+        }
+        GitIgnore::Off | GitIgnore::Nested => None,
+    };
+
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .parents(false)
+        .git_ignore(gitignore == GitIgnore::Nested)
+        .git_exclude(gitignore == GitIgnore::Nested)
+        .require_git(false)
+        .follow_links(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |entry| {
+            let name = entry.file_name();
+            if excluded.iter().any(|excluded| name == excluded.as_str()) {
+                return false;
+            }
+
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            root_ignore
+                .as_ref()
+                .is_none_or(|ignore| !ignore.matched(entry.path(), is_dir).is_ignore())
+        })
+        .build();
+
+    let mut entries = Vec::new();
+
+    for entry in walker {
+        let entry = entry.with_context(|| "failed to read a directory entry")?;
+        let path = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walked entries are inside the root")
+            .to_path_buf();
+
+        if path.as_os_str().is_empty() {
+            continue;
+        }
+
+        let file_type = entry.file_type();
+        entries.push(TreeEntry {
+            path,
+            is_dir: file_type.is_some_and(|t| t.is_dir()),
+            is_symlink: file_type.is_some_and(|t| t.is_symlink()),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Copy the tree under `src` into `dst` as listed by [`walk_tree`].
+/// Symbolic links are copied as links, with the same target.
+pub fn copy_tree(src: &Path, dst: &Path, excluded: &[&str], gitignore: GitIgnore) -> Result<()> {
+    let entries = walk_tree(src, excluded, gitignore)?;
+
+    fs::create_dir_all(dst)
+        .with_context(|| format!("failed to create directory: {}", dst.display()))?;
+
+    for entry in entries {
+        let src_path = src.join(&entry.path);
+        let dst_path = dst.join(&entry.path);
+
+        if entry.is_dir {
+            fs::create_dir_all(&dst_path)
+                .with_context(|| format!("failed to create directory: {}", dst_path.display()))?;
+        } else if entry.is_symlink {
+            let target = fs::read_link(&src_path)
+                .with_context(|| format!("failed to read symlink: {}", src_path.display()))?;
+            symlink(&target, &dst_path)
+                .with_context(|| format!("failed to copy symlink: {}", src_path.display()))?;
+        } else {
+            fs::copy(&src_path, &dst_path)
+                .with_context(|| format!("failed to copy file: {}", src_path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, path: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _path: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::test_utils::TemplateHome;
     use crate::test_utils::prelude::*;
 
     mod test_get_template_home {
@@ -112,6 +592,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::redundant_pattern_matching)]
         fn fail_to_determine() {
             with_vars(
                 [
@@ -129,34 +610,544 @@ mod tests {
     mod test_get_all_template_names {
         use super::*;
 
-        use std::fs;
-
         #[test]
         fn empty_dir() {
             let temp_dir = tempfile::tempdir().unwrap();
             let home = temp_dir.path();
 
             let result = get_all_template_names(home).unwrap();
-            assert_that!(result, empty());
+            assert_that!(result, is_empty());
         }
 
         #[test]
         fn work_correctly() {
-            let temp_dir = tempfile::tempdir().unwrap();
-            let home = temp_dir.path();
-
-            fs::create_dir(home.join("template1")).unwrap();
-            fs::create_dir(home.join("template2")).unwrap();
-            fs::File::create(home.join("template_ignored")).unwrap();
+            let mut home = TemplateHome::new();
+            home.push("template1", Some(""));
+            home.push("template2", Some(""));
+            fs::File::create(home.path().join("template_ignored")).unwrap();
+            fs::create_dir(home.path().join(".hidden")).unwrap();
 
-            let result = get_all_template_names(home).unwrap();
+            let result = get_all_template_names(home.path()).unwrap();
             assert_that!(result, {"template1", "template2"});
         }
 
+        #[test]
+        fn nested_namespaces() {
+            let mut home = TemplateHome::new();
+            home.push("rust/cli", Some(""));
+            home.push("rust/lib", Some(""));
+            home.push("web/frontend/vite", Some(""));
+            home.push("plain", Some(""));
+            home.push("plain/not_searched", Some(""));
+            fs::create_dir(home.path().join("empty_namespace")).unwrap();
+
+            let result = get_all_template_names(home.path()).unwrap();
+            assert_that!(
+                result,
+                {"rust/cli", "rust/lib", "web/frontend/vite", "plain"}
+            );
+        }
+
         #[test]
         fn invalid_home() {
             let result = get_all_template_names(Path::new("/invalid/path"));
             assert_that!(result, err(anything()));
         }
+
+        #[gtest]
+        fn without_meta() {
+            let mut home = TemplateHome::new();
+            home.push("rust/cli", Some(""));
+            home.push("rust/broken", None);
+            home.dirs()[1].create_template_dir();
+            fs::create_dir(home.path().join("empty_namespace")).unwrap();
+
+            expect_that!(get_all_template_names(home.path()).unwrap(), { "rust/cli" });
+            expect_that!(get_template_names_without_meta(home.path()).unwrap(), {
+                "rust/broken"
+            });
+        }
+    }
+
+    mod test_get_config_file {
+        use super::*;
+
+        use temp_env::with_vars;
+
+        #[test]
+        fn with_tempro_config() {
+            with_vars(
+                [
+                    ("TEMPRO_CONFIG", Some("/custom/config.toml")),
+                    ("TEMPRO_HOME", Some("/custom/tempro/home")),
+                ],
+                || {
+                    assert_that!(get_config_file(), ok(eq(Path::new("/custom/config.toml"))));
+                },
+            );
+        }
+
+        #[test]
+        fn fallback_template_home() {
+            with_vars(
+                [
+                    ("TEMPRO_CONFIG", None),
+                    ("TEMPRO_HOME", Some("/custom/tempro/home")),
+                ],
+                || {
+                    assert_that!(
+                        get_config_file(),
+                        ok(eq(Path::new("/custom/tempro/home/config.toml")))
+                    );
+                },
+            );
+        }
+    }
+
+    mod test_get_template_path {
+        use super::*;
+
+        use temp_env::with_vars;
+
+        #[test]
+        fn work_correctly() {
+            with_vars(
+                [
+                    ("TEMPRO_PATH", Some("/team/templates:/more/templates")),
+                    ("TEMPRO_HOME", Some("/custom/tempro/home")),
+                    ("XDG_DATA_DIRS", Some("/data1:/data2")),
+                ],
+                || {
+                    assert_that!(
+                        get_template_path().unwrap(),
+                        elements_are![
+                            eq(Path::new("/team/templates")),
+                            eq(Path::new("/more/templates")),
+                            eq(Path::new("/custom/tempro/home")),
+                            eq(Path::new("/data1/tempro")),
+                            eq(Path::new("/data2/tempro")),
+                        ]
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn fallback_xdg_data_dirs() {
+            with_vars(
+                [
+                    ("TEMPRO_PATH", None),
+                    ("TEMPRO_HOME", Some("/custom/tempro/home")),
+                    ("XDG_DATA_DIRS", None),
+                ],
+                || {
+                    assert_that!(
+                        get_template_path().unwrap(),
+                        elements_are![
+                            eq(Path::new("/custom/tempro/home")),
+                            eq(Path::new("/usr/local/share/tempro")),
+                            eq(Path::new("/usr/share/tempro")),
+                        ]
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn drop_empty_and_duplicated() {
+            with_vars(
+                [
+                    ("TEMPRO_PATH", Some("/custom/tempro/home::/team")),
+                    ("TEMPRO_HOME", Some("/custom/tempro/home")),
+                    ("XDG_DATA_DIRS", Some("/data")),
+                ],
+                || {
+                    assert_that!(
+                        get_template_path().unwrap(),
+                        elements_are![
+                            eq(Path::new("/custom/tempro/home")),
+                            eq(Path::new("/team")),
+                            eq(Path::new("/data/tempro")),
+                        ]
+                    );
+                },
+            );
+        }
+    }
+
+    mod test_get_project_template_dirs {
+        use super::*;
+
+        #[test]
+        fn nearest_first() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let root = temp_dir.path();
+
+            let nested = root.join("services/billing/src");
+            fs::create_dir_all(&nested).unwrap();
+            fs::create_dir_all(root.join(PROJECT_TEMPLATE_DIR)).unwrap();
+            fs::create_dir_all(root.join("services/billing").join(PROJECT_TEMPLATE_DIR)).unwrap();
+
+            let result = get_project_template_dirs(&nested);
+            assert_eq!(
+                result,
+                [
+                    root.join("services/billing").join(PROJECT_TEMPLATE_DIR),
+                    root.join(PROJECT_TEMPLATE_DIR),
+                ]
+            );
+        }
+
+        #[test]
+        fn none_found() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let result = get_project_template_dirs(temp_dir.path());
+            assert_that!(result, is_empty());
+        }
+    }
+
+    mod test_get_all_templates {
+        use super::*;
+
+        #[test]
+        fn mark_shadowed() {
+            let first = TemplateHome::single("foo", Some(""));
+            let mut second = TemplateHome::new();
+            second.push("foo", Some(""));
+            second.push("bar", Some(""));
+
+            let roots = [
+                first.path().to_path_buf(),
+                PathBuf::from("/invalid/path"),
+                second.path().to_path_buf(),
+            ];
+            let result = get_all_templates(&roots).unwrap();
+
+            assert_eq!(
+                result,
+                [
+                    TemplateEntry {
+                        name: "bar".into(),
+                        root: second.path().into(),
+                        shadowed: false,
+                    },
+                    TemplateEntry {
+                        name: "foo".into(),
+                        root: first.path().into(),
+                        shadowed: false,
+                    },
+                    TemplateEntry {
+                        name: "foo".into(),
+                        root: second.path().into(),
+                        shadowed: true,
+                    },
+                ]
+            );
+        }
+    }
+
+    mod test_find_template {
+        use super::*;
+
+        #[test]
+        fn first_root_wins() {
+            let first = TemplateHome::single("foo", Some(""));
+            let mut second = TemplateHome::new();
+            second.push("foo", Some(""));
+            second.push("bar", Some(""));
+            // a namespace is not a template
+            fs::create_dir(first.path().join("bar")).unwrap();
+
+            let roots = [first.path().to_path_buf(), second.path().to_path_buf()];
+
+            let foo = find_template(&roots, "foo").unwrap();
+            assert_eq!(foo.path(), first.path().join("foo"));
+
+            let bar = find_template(&roots, "bar").unwrap();
+            assert_eq!(bar.path(), second.path().join("bar"));
+        }
+
+        #[test]
+        fn nested_name() {
+            let home = TemplateHome::single("rust/cli", Some(""));
+            let result = find_template(&[home.path().to_path_buf()], "rust/cli").unwrap();
+            assert_eq!(result.path(), home.path().join("rust/cli"));
+        }
+
+        #[test]
+        fn not_found() {
+            let home = tempfile::tempdir().unwrap();
+            let result = find_template(&[home.path().to_path_buf()], "foo");
+            assert_that!(result, err(anything()));
+        }
+
+        fn setup_versions() -> TemplateHome {
+            let mut home = TemplateHome::new();
+            home.push("service", Some(r#"description = "Unversioned""#));
+            home.push("service@1.4", Some(r#"description = "1.4""#));
+            home.push(
+                "service@1.10",
+                Some("description = \"1.10\"\nversion = \"1.10.2\""),
+            );
+            home.push("service@2.0", Some(r#"description = "2.0""#));
+            home.push("service-other@3.0", Some(r#"description = "Other""#));
+            home.push("rust/lib@0.1", Some(r#"description = "Nested""#));
+            home.push("rust/lib@0.2", Some(r#"description = "Nested""#));
+            home
+        }
+
+        #[gtest]
+        fn versions() {
+            let home = setup_versions();
+            let roots = [home.path().to_path_buf()];
+            let find = |name| find_template(&roots, name).map(|entry| entry.name);
+
+            expect_that!(find("service"), ok(eq("service@2.0")));
+            expect_that!(find("service@^1"), ok(eq("service@1.10")));
+            expect_that!(find("service@~1.4"), ok(eq("service@1.4")));
+            expect_that!(find("service@1.4"), ok(eq("service@1.4")));
+            expect_that!(find("service@>=2, <3"), ok(eq("service@2.0")));
+            expect_that!(find("service@^3"), err(anything()));
+            expect_that!(find("service@not a version"), err(anything()));
+            expect_that!(find("rust/lib"), ok(eq("rust/lib@0.2")));
+            expect_that!(find("rust/lib@0.1"), ok(eq("rust/lib@0.1")));
+        }
+
+        #[gtest]
+        fn unversioned_ranks_lowest() {
+            let mut home = TemplateHome::new();
+            home.push("service", Some(r#"description = "Unversioned""#));
+            home.push("service@0.1", Some(r#"description = "0.1""#));
+            let roots = [home.path().to_path_buf()];
+
+            expect_that!(
+                find_template(&roots, "service").map(|entry| entry.name),
+                ok(eq("service@0.1"))
+            );
+        }
+
+        #[gtest]
+        fn versions_within_first_root() {
+            let mut first = TemplateHome::new();
+            first.push("service@1.0", Some(r#"description = "1.0""#));
+            let mut second = TemplateHome::new();
+            second.push("service@2.0", Some(r#"description = "2.0""#));
+            second.push("other@1.0", Some(r#"description = "1.0""#));
+            let roots = [first.path().to_path_buf(), second.path().to_path_buf()];
+            let find = |name| find_template(&roots, name);
+
+            expect_that!(
+                find("service"),
+                ok(eq(&TemplateEntry {
+                    name: "service@1.0".into(),
+                    root: first.path().into(),
+                    shadowed: false,
+                }))
+            );
+            expect_that!(
+                find("service@^1"),
+                ok(field!(TemplateEntry.name, eq("service@1.0")))
+            );
+            expect_that!(find("service@^2"), err(anything()));
+            expect_that!(find("service@2.0"), err(anything()));
+            expect_that!(
+                find("other"),
+                ok(field!(TemplateEntry.root, eq(second.path())))
+            );
+        }
+
+        #[test]
+        fn invalid_name() {
+            let home = TemplateHome::single("foo", Some(""));
+            let roots = [home.path().join("foo")];
+            for name in ["", "..", "../foo", "foo/", "/foo", "foo//bar", ".hidden"] {
+                assert_that!(find_template(&roots, name), err(anything()));
+            }
+        }
+    }
+
+    mod test_locate_template {
+        use super::*;
+
+        #[test]
+        fn it_works() {
+            let home = TemplateHome::single("service@1.0", Some(""));
+
+            assert_that!(
+                locate_template(home.path(), "service@1.0"),
+                ok(eq(&home.path().join("service@1.0")))
+            );
+            assert_that!(locate_template(home.path(), "service"), err(anything()));
+            assert_that!(locate_template(home.path(), "../service"), err(anything()));
+        }
+    }
+
+    mod test_validate_namespaces {
+        use super::*;
+
+        #[gtest]
+        fn it_works() {
+            let mut home = TemplateHome::new();
+            home.push("rust/cli", Some(""));
+            fs::write(home.path().join("file"), "").unwrap();
+
+            expect_that!(validate_namespaces(home.path(), "foo"), ok(()));
+            expect_that!(validate_namespaces(home.path(), "rust/lib"), ok(()));
+            expect_that!(validate_namespaces(home.path(), "new/ns/lib"), ok(()));
+            expect_that!(
+                validate_namespaces(home.path(), "rust/cli/nested"),
+                err(anything())
+            );
+            expect_that!(
+                validate_namespaces(home.path(), "file/lib"),
+                err(anything())
+            );
+        }
+    }
+
+    mod test_remove_empty_namespaces {
+        use super::*;
+
+        #[test]
+        fn it_works() {
+            let mut home = TemplateHome::new();
+            home.push("a/b/c/template", Some(""));
+            home.push("a/other", Some(""));
+
+            let path = home.path().join("a/b/c/template");
+            fs::remove_dir_all(&path).unwrap();
+            remove_empty_namespaces(home.path(), &path).unwrap();
+
+            assert!(!home.path().join("a/b").exists());
+            assert!(home.path().join("a/other").exists());
+        }
+    }
+
+    mod test_walk_tree {
+        use super::*;
+
+        use std::os::unix::fs::symlink;
+
+        fn setup() -> tempfile::TempDir {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let root = temp_dir.path();
+
+            fs::create_dir_all(root.join("src/nested")).unwrap();
+            fs::create_dir_all(root.join("target/debug")).unwrap();
+            fs::create_dir_all(root.join(".git")).unwrap();
+            fs::write(root.join(".gitignore"), "*.log\n/root-only.txt\n").unwrap();
+            fs::write(root.join("src/main.rs"), "").unwrap();
+            fs::write(root.join("src/nested/.gitignore"), "ignored.txt\n").unwrap();
+            fs::write(root.join("src/nested/ignored.txt"), "").unwrap();
+            fs::write(root.join("src/nested/kept.txt"), "").unwrap();
+            fs::write(root.join("src/root-only.txt"), "").unwrap();
+            fs::write(root.join("root-only.txt"), "").unwrap();
+            fs::write(root.join("debug.log"), "").unwrap();
+            fs::write(root.join("target/debug/app"), "").unwrap();
+            fs::write(root.join(".git/HEAD"), "").unwrap();
+            symlink("src", root.join("link")).unwrap();
+
+            temp_dir
+        }
+
+        fn walk(root: &Path, gitignore: GitIgnore) -> Vec<String> {
+            walk_tree(root, &[".git", "target"], gitignore)
+                .unwrap()
+                .iter()
+                .map(|entry| entry.path.to_str().unwrap().to_string())
+                .collect()
+        }
+
+        #[test]
+        fn nested() {
+            let temp_dir = setup();
+            assert_eq!(
+                walk(temp_dir.path(), GitIgnore::Nested),
+                [
+                    ".gitignore",
+                    "link",
+                    "src",
+                    "src/main.rs",
+                    "src/nested",
+                    "src/nested/.gitignore",
+                    "src/nested/kept.txt",
+                    "src/root-only.txt",
+                ]
+            );
+        }
+
+        #[test]
+        fn root_only() {
+            let temp_dir = setup();
+            assert_eq!(
+                walk(temp_dir.path(), GitIgnore::Root),
+                [
+                    ".gitignore",
+                    "link",
+                    "src",
+                    "src/main.rs",
+                    "src/nested",
+                    "src/nested/.gitignore",
+                    "src/nested/ignored.txt",
+                    "src/nested/kept.txt",
+                    "src/root-only.txt",
+                ]
+            );
+        }
+
+        #[test]
+        fn off() {
+            let temp_dir = setup();
+            assert_eq!(
+                walk(temp_dir.path(), GitIgnore::Off),
+                [
+                    ".gitignore",
+                    "debug.log",
+                    "link",
+                    "root-only.txt",
+                    "src",
+                    "src/main.rs",
+                    "src/nested",
+                    "src/nested/.gitignore",
+                    "src/nested/ignored.txt",
+                    "src/nested/kept.txt",
+                    "src/root-only.txt",
+                ]
+            );
+        }
+
+        #[gtest]
+        fn symlinks_not_followed() {
+            let temp_dir = setup();
+            let entries = walk_tree(temp_dir.path(), &[], GitIgnore::Off).unwrap();
+
+            expect_that!(
+                entries,
+                contains(eq(&TreeEntry {
+                    path: PathBuf::from("link"),
+                    is_dir: false,
+                    is_symlink: true,
+                }))
+            );
+            expect_that!(
+                entries,
+                not(contains(field!(
+                    TreeEntry.path,
+                    eq(Path::new("link/main.rs"))
+                )))
+            );
+        }
+
+        #[test]
+        fn ignore_files_outside_root() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            fs::write(temp_dir.path().join(".gitignore"), "*\n").unwrap();
+            let root = temp_dir.path().join("root");
+            fs::create_dir(&root).unwrap();
+            fs::write(root.join("file.txt"), "").unwrap();
+
+            assert_eq!(walk(&root, GitIgnore::Nested), ["file.txt"]);
+            assert_eq!(walk(&root, GitIgnore::Root), ["file.txt"]);
+        }
     }
 }
@@ -0,0 +1,107 @@
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+/// Clone `source` into `dest`, checking out `rev` (a branch or tag) if given.
+pub fn clone(source: &str, dest: &Path, rev: Option<&str>) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args(["clone", "--quiet"]);
+    if let Some(rev) = rev {
+        command.args(["--branch", rev]);
+    }
+    command.arg("--").arg(source).arg(dest);
+
+    run(command)?;
+    Ok(())
+}
+
+/// Get the full hash of the commit checked out in `repo`.
+pub fn head_commit(repo: &Path) -> Result<String> {
+    run(git(repo, ["rev-parse", "HEAD"]))
+}
+
+/// Fetch `rev` (a branch, tag or `HEAD`) from `source` into `repo`
+/// and return the hash of the fetched commit.
+pub fn fetch(repo: &Path, source: &str, rev: &str) -> Result<String> {
+    run(git(repo, ["fetch", "--quiet", "--", source, rev]))?;
+    run(git(repo, ["rev-parse", "FETCH_HEAD^{commit}"]))
+}
+
+/// Check whether the working tree of `repo` has no local modifications.
+/// Ignored files are not taken into account.
+pub fn is_clean(repo: &Path) -> Result<bool> {
+    Ok(run(git(repo, ["status", "--porcelain"]))?.is_empty())
+}
+
+/// Check whether `ancestor` is an ancestor of (or the same as) `commit`.
+pub fn is_ancestor(repo: &Path, ancestor: &str, commit: &str) -> Result<bool> {
+    let mut command = git(repo, ["merge-base", "--is-ancestor", ancestor, commit]);
+    let output = command
+        .output()
+        .with_context(|| format!("failed to run {command:?}"))?;
+
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => bail!(
+            "{command:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+/// Reset `repo` to `commit`, discarding any local modifications.
+pub fn reset_hard(repo: &Path, commit: &str) -> Result<()> {
+    run(git(repo, ["reset", "--quiet", "--hard", commit]))?;
+    Ok(())
+}
+
+/// Add `pattern` to the repository-local exclude file of `repo`,
+/// so that matching files are never reported as local modifications.
+pub fn exclude(repo: &Path, pattern: &str) -> Result<()> {
+    let path = PathBuf::from(run(git(repo, ["rev-parse", "--git-path", "info/exclude"]))?);
+    let path = repo.join(path);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open exclude file: {}", path.display()))?;
+    writeln!(file, "/{pattern}")
+        .with_context(|| format!("failed to write exclude file: {}", path.display()))
+}
+
+fn git<I, S>(repo: &Path, args: I) -> Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo).args(args);
+    command
+}
+
+/// Run a git command and return its trimmed standard output.
+fn run(mut command: Command) -> Result<String> {
+    let output = command
+        .output()
+        .with_context(|| format!("failed to run {command:?}"))?;
+
+    if !output.status.success() {
+        bail!(
+            "{command:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
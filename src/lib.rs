@@ -1,6 +1,11 @@
+pub mod archive;
 pub mod cli;
 pub mod command;
+pub mod config;
 pub mod file;
+pub mod git;
+pub mod lsp;
+pub mod registry;
 pub mod template;
 
 #[cfg(test)]
@@ -0,0 +1,418 @@
+//! A language server for template authoring, reporting `check` problems in meta.toml.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::{
+    DiagnosticSeverity, Position, PublishDiagnosticsParams, Range, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+
+use crate::template::{Diagnostic, Level, Template};
+
+/// Serve LSP over stdin and stdout until the client exits.
+pub fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(&connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+pub fn serve(connection: &Connection) -> Result<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server {
+        connection,
+        documents: HashMap::new(),
+    };
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let message = format!("unsupported request: {}", request.method);
+                let response =
+                    Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message);
+                connection.sender.send(response.into())?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// Content of the open documents, which may differ from the files on disk.
+    documents: HashMap<Uri, String>,
+}
+
+impl Server<'_> {
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params) = extract::<DidOpenTextDocument>(notification) else {
+                    return Ok(());
+                };
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                self.publish_diagnostics(&document.uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(params) = extract::<DidChangeTextDocument>(notification) else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                // With full sync, the last change holds the whole document.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(uri.clone(), change.text);
+                }
+                self.publish_diagnostics(&uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let Some(params) = extract::<DidCloseTextDocument>(notification) else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.send_diagnostics(uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &Uri) -> Result<()> {
+        let (Some(path), Some(text)) = (to_path(uri), self.documents.get(uri)) else {
+            return Ok(());
+        };
+        if path
+            .file_name()
+            .is_none_or(|name| name != Template::META_FILE)
+        {
+            return Ok(());
+        }
+
+        let diagnostics = check_meta(&path, text);
+        self.send_diagnostics(uri.clone(), diagnostics)
+    }
+
+    fn send_diagnostics(&self, uri: Uri, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
+
+/// Get the parameters of a notification, logging and dropping malformed ones
+/// so that a misbehaving client does not stop the server.
+fn extract<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> Option<N::Params> {
+    match notification.extract(N::METHOD) {
+        Ok(params) => Some(params),
+        Err(e) => {
+            eprintln!("ignoring notification: {e}");
+            None
+        }
+    }
+}
+
+/// Check the meta file at `path` with the given content the way `check` does.
+fn check_meta(path: &Path, text: &str) -> Vec<lsp_types::Diagnostic> {
+    let location = path.parent().unwrap_or(Path::new(""));
+
+    let template = match Template::parse(location, text) {
+        Ok(template) => template,
+        Err(e) => {
+            return vec![match e.downcast_ref::<Diagnostic>() {
+                Some(diagnostic) => to_lsp_diagnostic(diagnostic, text),
+                None => error_at_start(format!("{e:#}")),
+            }];
+        }
+    };
+
+    let mut diagnostics: Vec<_> = template
+        .diagnostics(true)
+        .iter()
+        .map(|diagnostic| to_lsp_diagnostic(diagnostic, text))
+        .collect();
+
+    if let Err(e) = template.verify() {
+        diagnostics.push(error_at_start(format!("{e:#}")));
+    }
+
+    diagnostics
+}
+
+fn to_lsp_diagnostic(diagnostic: &Diagnostic, text: &str) -> lsp_types::Diagnostic {
+    let range = diagnostic.span().map_or_else(Range::default, |span| Range {
+        start: to_position(text, span.start),
+        end: to_position(text, span.end),
+    });
+
+    let severity = match diagnostic.level() {
+        Level::Error => DiagnosticSeverity::ERROR,
+        Level::Warning => DiagnosticSeverity::WARNING,
+    };
+
+    let message = match diagnostic.help() {
+        Some(help) => format!("{}\nhelp: {help}", diagnostic.message()),
+        None => diagnostic.message().to_string(),
+    };
+
+    lsp_types::Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("tempro".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+fn error_at_start(message: String) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("tempro".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Convert a byte offset into an LSP position, which counts UTF-16 code units.
+fn to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let before = text.get(..offset).unwrap_or(text);
+
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+fn to_path(uri: &Uri) -> Option<PathBuf> {
+    if uri
+        .scheme()
+        .is_none_or(|scheme| !scheme.as_str().eq_ignore_ascii_case("file"))
+    {
+        return None;
+    }
+
+    let path = uri.path().as_estr().decode().into_string().ok()?;
+    Some(PathBuf::from(path.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::str::FromStr;
+    use std::thread;
+
+    use lsp_server::Request;
+    use lsp_types::request::{Initialize, Request as _, Shutdown};
+    use lsp_types::{
+        DidChangeTextDocumentParams, DidOpenTextDocumentParams, InitializeParams,
+        TextDocumentContentChangeEvent, TextDocumentItem, VersionedTextDocumentIdentifier,
+    };
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    /// An in-process client talking to a server running on another thread.
+    struct Client {
+        connection: Connection,
+        server: Option<thread::JoinHandle<anyhow::Result<()>>>,
+    }
+
+    impl Client {
+        fn start() -> Self {
+            let (client, server) = Connection::memory();
+            let server = thread::spawn(move || serve(&server));
+            let client = Self {
+                connection: client,
+                server: Some(server),
+            };
+
+            client.request(Initialize::METHOD, InitializeParams::default());
+            client.notify("initialized", serde_json::json!({}));
+            client
+        }
+
+        fn request(&self, method: &str, params: impl serde::Serialize) -> Response {
+            let request = Request::new(1.into(), method.to_string(), params);
+            self.connection.sender.send(request.into()).unwrap();
+            match self.connection.receiver.recv().unwrap() {
+                Message::Response(response) => response,
+                message => panic!("expected a response, got {message:?}"),
+            }
+        }
+
+        fn notify(&self, method: &str, params: impl serde::Serialize) {
+            let notification = Notification::new(method.to_string(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        fn diagnostics(&self) -> PublishDiagnosticsParams {
+            match self.connection.receiver.recv().unwrap() {
+                Message::Notification(notification) => {
+                    assert_eq!(notification.method, PublishDiagnostics::METHOD);
+                    serde_json::from_value(notification.params).unwrap()
+                }
+                message => panic!("expected diagnostics, got {message:?}"),
+            }
+        }
+
+        fn open(&self, uri: &Uri, text: &str) {
+            let params = DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "toml".to_string(),
+                    1,
+                    text.to_string(),
+                ),
+            };
+            self.notify(DidOpenTextDocument::METHOD, params);
+        }
+
+        fn change(&self, uri: &Uri, text: &str) {
+            let params = DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: text.to_string(),
+                }],
+            };
+            self.notify(DidChangeTextDocument::METHOD, params);
+        }
+
+        fn stop(mut self) {
+            let response = self.request(Shutdown::METHOD, ());
+            assert!(response.error.is_none());
+            self.notify("exit", ());
+            self.server.take().unwrap().join().unwrap().unwrap();
+        }
+    }
+
+    fn setup() -> (TemplateHome, Uri) {
+        let home = TemplateHome::single("test-template", Some(r#"description = "Test""#));
+        home.dirs()[0].create_template_dir();
+        let meta_file = home.dirs()[0].path().join(Template::META_FILE);
+        let uri = Uri::from_str(&format!("file://{}", meta_file.display())).unwrap();
+        (home, uri)
+    }
+
+    #[gtest]
+    fn diagnostics() {
+        let (_home, uri) = setup();
+        let client = Client::start();
+
+        client.open(&uri, "descripton = \"Test\"\n");
+        let params = client.diagnostics();
+        expect_eq!(params.uri, uri);
+        assert_that!(params.diagnostics, len(eq(1)));
+        let diagnostic = &params.diagnostics[0];
+        expect_eq!(
+            diagnostic.message,
+            "missing field `description`\nhelp: did you mean `description`?"
+        );
+        expect_eq!(
+            diagnostic.range,
+            Range::new(Position::new(0, 0), Position::new(0, 10))
+        );
+        expect_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+
+        client.change(&uri, "description = \"Test\"\nauthor = \"me\"\n");
+        let params = client.diagnostics();
+        assert_that!(params.diagnostics, len(eq(1)));
+        expect_that!(
+            params.diagnostics[0].message,
+            starts_with("unknown key `author`")
+        );
+        expect_eq!(
+            params.diagnostics[0].range,
+            Range::new(Position::new(1, 0), Position::new(1, 6))
+        );
+
+        client.change(&uri, "description = \"Test\"\n");
+        expect_that!(client.diagnostics().diagnostics, is_empty());
+
+        client.stop();
+    }
+
+    #[gtest]
+    fn malformed_notification() {
+        let (_home, uri) = setup();
+        let client = Client::start();
+
+        client.notify(
+            DidOpenTextDocument::METHOD,
+            serde_json::json!({ "textDocument": 1 }),
+        );
+        client.open(&uri, "descripton = \"Test\"\n");
+        expect_that!(client.diagnostics().diagnostics, len(eq(1)));
+
+        client.stop();
+    }
+
+    #[gtest]
+    fn template_tree() {
+        let (home, uri) = setup();
+        fs::remove_dir(home.dirs()[0].path().join(Template::TEMPLATE_DIR)).unwrap();
+        let client = Client::start();
+
+        client.open(&uri, "description = \"Test\"\n");
+        let params = client.diagnostics();
+        assert_that!(params.diagnostics, len(eq(1)));
+        expect_that!(
+            params.diagnostics[0].message,
+            starts_with("template directory")
+        );
+
+        client.stop();
+    }
+
+    #[gtest]
+    fn other_files_ignored() {
+        let (home, _) = setup();
+        let path = home.dirs()[0].path().join("template/README.md");
+        let uri = Uri::from_str(&format!("file://{}", path.display())).unwrap();
+        let client = Client::start();
+
+        client.open(&uri, "# Readme");
+        let response = client.request("textDocument/hover", serde_json::json!({}));
+        expect_that!(response.error, some(anything()));
+
+        client.stop();
+    }
+
+    #[gtest]
+    fn test_to_position() {
+        let text = "a = \"é😀\"\nb";
+        expect_eq!(to_position(text, 0), Position::new(0, 0));
+        expect_eq!(to_position(text, 11), Position::new(0, 8));
+        expect_eq!(to_position(text, 13), Position::new(1, 0));
+        expect_eq!(to_position(text, 100), Position::new(1, 1));
+    }
+}
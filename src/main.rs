@@ -3,16 +3,35 @@ use clap::Parser;
 
 use tempro::cli::{Cli, Command};
 use tempro::command;
+use tempro::config::Config;
 use tempro::file;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     let home = file::get_template_home().context("failed to get template home")?;
+    let roots = file::get_template_path().context("failed to get template search path")?;
+    // only the commands resolving template names read the config
+    let config = || Config::load(&file::get_config_file().context("failed to get config file")?);
 
     match &cli.command {
-        Command::List(args) => command::handle_list_command(&home, args),
-        Command::Check(args) => command::handle_check_command(&home, args),
-        Command::Apply(args) => command::handle_apply_command(&home, args),
+        Command::List(args) => command::handle_list_command(&roots, args),
+        Command::Check(args) => command::handle_check_command(&roots, &config()?, args),
+        Command::Show(args) => command::handle_show_command(&roots, &config()?, args),
+        Command::Apply(args) => command::handle_apply_command(&roots, &config()?, args),
+        Command::Render(args) => command::handle_render_command(&roots, &config()?, args),
+        Command::Add(args) => command::handle_add_command(&home, args),
+        Command::Pull(args) => command::handle_pull_command(&home, args),
+        Command::Pack(args) => command::handle_pack_command(&roots, args),
+        Command::Search(args) => command::handle_search_command(args),
+        Command::New(args) => command::handle_new_command(&home, args),
+        Command::Capture(args) => command::handle_capture_command(&home, args),
+        Command::Rm(args) => command::handle_rm_command(&home, args),
+        Command::Mv(args) => command::handle_mv_command(&home, args),
+        Command::Cp(args) => command::handle_cp_command(&roots, &home, args),
+        Command::Edit(args) => command::handle_edit_command(&roots, args),
+        Command::Test(args) => command::handle_test_command(&roots, args),
+        Command::Schema => command::handle_schema_command(),
+        Command::Lsp => command::handle_lsp_command(),
     }
 }
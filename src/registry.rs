@@ -0,0 +1,218 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// A curated index of templates and where to install them from.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub templates: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegistryEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// A git URL or path, or the path of a template archive.
+    /// Relative paths are relative to the registry index file.
+    pub source: String,
+    /// The branch or tag to install, for git sources.
+    pub rev: Option<String>,
+}
+
+impl Registry {
+    /// Load a registry index from a local path or a `file://` URL.
+    /// Files ending with `.json` are parsed as JSON, anything else as TOML.
+    pub fn load(location: &str) -> Result<Self> {
+        let path = index_path(location)?;
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read registry index: {}", path.display()))?;
+
+        let mut registry: Self = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .with_context(|| format!("failed to parse registry index: {}", path.display()))?
+        } else {
+            toml::from_str(&content)
+                .with_context(|| format!("failed to parse registry index: {}", path.display()))?
+        };
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for entry in &mut registry.templates {
+            entry.source = resolve_source(base, &entry.source);
+        }
+
+        Ok(registry)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&RegistryEntry> {
+        self.templates.iter().find(|entry| entry.name == name)
+    }
+
+    /// Find the templates whose name, description or tags contain `query`, ignoring case.
+    pub fn search(&self, query: &str) -> Vec<&RegistryEntry> {
+        let query = query.to_lowercase();
+        self.templates
+            .iter()
+            .filter(|entry| {
+                entry.name.to_lowercase().contains(&query)
+                    || entry.description.to_lowercase().contains(&query)
+                    || entry
+                        .tags
+                        .iter()
+                        .any(|tag| tag.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
+fn index_path(location: &str) -> Result<PathBuf> {
+    if let Some(path) = location.strip_prefix("file://") {
+        return Ok(PathBuf::from(path));
+    }
+
+    if location.contains("://") {
+        bail!("only local registry indexes are supported: {location}");
+    }
+
+    Ok(PathBuf::from(location))
+}
+
+/// Resolve a relative local source against the directory of the index.
+/// URLs and scp-like git addresses (`host:path`) are kept as is.
+fn resolve_source(base: &Path, source: &str) -> String {
+    let is_remote = source.contains("://") || source.split('/').next().unwrap().contains(':');
+    if is_remote || Path::new(source).is_absolute() {
+        return source.to_string();
+    }
+
+    base.join(source).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    const TOML_INDEX: &str = r#"
+[[templates]]
+name = "rust-cli"
+description = "Command line application"
+tags = ["rust", "cli"]
+source = "https://git.example.com/templates/rust-cli.git"
+rev = "v1.2"
+
+[[templates]]
+name = "web/vite"
+description = "Vite frontend"
+tags = ["web", "typescript"]
+source = "archives/vite.tar.gz"
+"#;
+
+    const JSON_INDEX: &str = r#"{
+  "templates": [
+    {
+      "name": "rust-lib",
+      "description": "Rust library",
+      "tags": ["rust"],
+      "source": "git@git.example.com:templates/rust-lib.git"
+    }
+  ]
+}"#;
+
+    fn setup() -> (tempfile::TempDir, Registry) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = temp_dir.path().join("index.toml");
+        fs::write(&index, TOML_INDEX).unwrap();
+        let registry = Registry::load(index.to_str().unwrap()).unwrap();
+        (temp_dir, registry)
+    }
+
+    #[gtest]
+    fn load_toml() {
+        let (temp_dir, registry) = setup();
+
+        expect_eq!(
+            registry.templates,
+            [
+                RegistryEntry {
+                    name: "rust-cli".to_string(),
+                    description: "Command line application".to_string(),
+                    tags: vec!["rust".to_string(), "cli".to_string()],
+                    source: "https://git.example.com/templates/rust-cli.git".to_string(),
+                    rev: Some("v1.2".to_string()),
+                },
+                RegistryEntry {
+                    name: "web/vite".to_string(),
+                    description: "Vite frontend".to_string(),
+                    tags: vec!["web".to_string(), "typescript".to_string()],
+                    source: temp_dir
+                        .path()
+                        .join("archives/vite.tar.gz")
+                        .display()
+                        .to_string(),
+                    rev: None,
+                },
+            ]
+        );
+    }
+
+    #[gtest]
+    fn load_json_from_file_url() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = temp_dir.path().join("index.json");
+        fs::write(&index, JSON_INDEX).unwrap();
+
+        let registry = Registry::load(&format!("file://{}", index.display())).unwrap();
+
+        expect_that!(
+            registry.find("rust-lib"),
+            some(field!(
+                RegistryEntry.source,
+                eq("git@git.example.com:templates/rust-lib.git")
+            ))
+        );
+    }
+
+    #[test]
+    fn load_remote() {
+        assert_that!(
+            Registry::load("https://example.com/index.toml"),
+            err(anything())
+        );
+    }
+
+    #[test]
+    fn load_invalid() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = temp_dir.path().join("index.toml");
+        fs::write(&index, "[[templates]]\nname = 1").unwrap();
+
+        assert_that!(Registry::load(index.to_str().unwrap()), err(anything()));
+    }
+
+    #[gtest]
+    fn search() {
+        let (_temp_dir, registry) = setup();
+
+        let names = |query| {
+            registry
+                .search(query)
+                .into_iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        expect_eq!(names("RUST"), ["rust-cli"]);
+        expect_eq!(names("frontend"), ["web/vite"]);
+        expect_eq!(names("script"), ["web/vite"]);
+        expect_eq!(names(""), ["rust-cli", "web/vite"]);
+        expect_that!(names("python"), is_empty());
+    }
+}
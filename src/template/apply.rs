@@ -1,15 +1,77 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
 
 use super::Template;
+use crate::file::TreeEntry;
 
 impl Template {
     pub fn apply(&self, path: &Path) -> Result<()> {
         let template_dir = self.location().join(Self::TEMPLATE_DIR);
         copy_dir(&template_dir, path)
     }
+
+    /// List the files and directories [`apply`](Self::apply) creates, relative to the target,
+    /// parents first and sorted by name.
+    pub fn entries(&self) -> Result<Vec<TreeEntry>> {
+        let template_dir = self.location().join(Self::TEMPLATE_DIR);
+        let mut entries = Vec::new();
+        collect_entries(&template_dir, Path::new(""), &mut entries)?;
+        Ok(entries)
+    }
+
+    /// Render a single file, given by its path inside the template directory.
+    pub fn render_file(&self, path: &Path) -> Result<Vec<u8>> {
+        if path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            bail!("path {} is not inside the template", path.display());
+        }
+
+        let template_dir = self.location().join(Self::TEMPLATE_DIR);
+        let file = template_dir.join(path);
+        if !file.is_file() {
+            bail!("{} is not a file in the template", path.display());
+        }
+
+        // a symlink may still point outside of the template
+        if !dst_is_under_src(&template_dir, &file)? {
+            bail!("path {} is not inside the template", path.display());
+        }
+
+        fs::read(&file).with_context(|| format!("failed to read file: {}", file.display()))
+    }
+
+    /// Get the path of the single file of a snippet template, relative to the template
+    /// directory.
+    pub fn snippet_file(&self) -> Result<PathBuf> {
+        let template_dir = self.location().join(Self::TEMPLATE_DIR);
+
+        let mut files = Vec::new();
+        for entry in template_dir
+            .read_dir()
+            .with_context(|| format!("failed to read directory: {}", template_dir.display()))?
+        {
+            let entry = entry.with_context(|| "failed to read a directory entry")?;
+            files.push(entry.file_name());
+        }
+
+        match files.as_slice() {
+            [file] if template_dir.join(file).is_file() => Ok(PathBuf::from(file)),
+            _ => bail!(
+                "snippet template {} must contain exactly one file in {}/",
+                self.name(),
+                Self::TEMPLATE_DIR
+            ),
+        }
+    }
+
+    /// Render the single file of a snippet template.
+    pub fn render_snippet(&self) -> Result<Vec<u8>> {
+        self.render_file(&self.snippet_file()?)
+    }
 }
 
 fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
@@ -52,6 +114,36 @@ fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Collect the entries under `dir` the way [`copy_dir`] walks it.
+fn collect_entries(dir: &Path, prefix: &Path, entries: &mut Vec<TreeEntry>) -> Result<()> {
+    let mut children = Vec::new();
+    for entry in dir
+        .read_dir()
+        .with_context(|| format!("failed to read source directory: {}", dir.display()))?
+    {
+        let entry = entry.with_context(|| "failed to read a directory entry")?;
+        children.push((entry.file_name(), entry.path()));
+    }
+    children.sort();
+
+    for (name, src_path) in children {
+        let path = prefix.join(name);
+        // symlinks are followed and copied as what they point to
+        let is_dir = src_path.is_dir();
+
+        entries.push(TreeEntry {
+            path: path.clone(),
+            is_dir,
+            is_symlink: false,
+        });
+        if is_dir {
+            collect_entries(&src_path, &path, entries)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn dst_is_under_src(src: &Path, dst: &Path) -> Result<bool> {
     let src = src.canonicalize()?;
     let dst = dst.canonicalize()?;
@@ -107,6 +199,21 @@ mod tests {
         expect_that!(target_path.join("dir/file.txt"), file("Some content"));
     }
 
+    #[test]
+    fn entries_match_applied_tree() {
+        let (_home, template) = setup_home();
+        let template_dir = template.location().join(Template::TEMPLATE_DIR);
+        fs::write(template_dir.join(".gitignore"), "*.txt\n").unwrap();
+        std::os::unix::fs::symlink("dir", template_dir.join("link")).unwrap();
+
+        let (_temp_dir, target_path) = setup_target();
+        template.apply(&target_path).unwrap();
+
+        let applied =
+            crate::file::walk_tree(&target_path, &[], crate::file::GitIgnore::Off).unwrap();
+        assert_eq!(template.entries().unwrap(), applied);
+    }
+
     #[test]
     fn template_dir_missing() {
         let home = TemplateHome::single("test template", Some(r#"description = "Test""#));
@@ -155,4 +262,87 @@ mod tests {
 
         assert_that!(template.apply(&target_path), err(anything()));
     }
+
+    mod test_render_file {
+        use super::*;
+
+        use std::os::unix::fs::symlink;
+
+        #[gtest]
+        fn it_works() {
+            let (_home, template) = setup_home();
+
+            expect_that!(
+                template.render_file(Path::new("dir/file.txt")),
+                ok(eq(b"Some content"))
+            );
+            expect_that!(
+                template.render_file(Path::new("./file.txt")),
+                ok(eq(b"Some content"))
+            );
+        }
+
+        #[test]
+        fn not_a_file() {
+            let (_home, template) = setup_home();
+
+            for path in ["dir", "missing.txt", "", "../meta.toml", "/etc/passwd"] {
+                assert_that!(
+                    template.render_file(Path::new(path)),
+                    err(anything()),
+                    "{path}"
+                );
+            }
+        }
+
+        #[test]
+        fn symlink_outside_template() {
+            let (_home, template) = setup_home();
+            let template_dir = template.location().join(Template::TEMPLATE_DIR);
+            symlink("../meta.toml", template_dir.join("meta")).unwrap();
+
+            assert_that!(template.render_file(Path::new("meta")), err(anything()));
+        }
+    }
+
+    mod test_render_snippet {
+        use super::*;
+
+        fn setup_snippet(files: &[&str]) -> (TemplateHome, Template) {
+            let home = TemplateHome::single(
+                "header",
+                Some("description = \"Header\"\nkind = \"snippet\""),
+            );
+            let template_dir = home.dirs()[0].create_template_dir();
+            for file in files {
+                fs::write(template_dir.join(file), "// SPDX-License-Identifier: MIT\n").unwrap();
+            }
+            let template = Template::load(home.dirs()[0].path()).unwrap();
+            (home, template)
+        }
+
+        #[gtest]
+        fn it_works() {
+            let (_home, template) = setup_snippet(&["header.rs"]);
+
+            expect_that!(template.snippet_file(), ok(eq(Path::new("header.rs"))));
+            expect_that!(
+                template.render_snippet(),
+                ok(eq(b"// SPDX-License-Identifier: MIT\n"))
+            );
+        }
+
+        #[test]
+        fn not_a_single_file() {
+            let (_home, template) = setup_snippet(&[]);
+            assert_that!(template.render_snippet(), err(anything()));
+
+            let (_home, template) = setup_snippet(&["a", "b"]);
+            assert_that!(template.render_snippet(), err(anything()));
+
+            let (home, template) = setup_snippet(&[]);
+            fs::create_dir(home.dirs()[0].path().join("template/dir")).unwrap();
+            assert_that!(template.render_snippet(), err(anything()));
+        }
+    }
 }
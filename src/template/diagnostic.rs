@@ -0,0 +1,219 @@
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// A problem in a template file, rendered with the offending source line.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    level: Level,
+    path: PathBuf,
+    source: String,
+    span: Option<Range<usize>>,
+    message: String,
+    label: Option<String>,
+    help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(path: &Path, source: &str, span: Option<Range<usize>>, message: &str) -> Self {
+        Self {
+            level: Level::Error,
+            path: path.to_path_buf(),
+            source: source.to_string(),
+            span,
+            message: message.trim().to_string(),
+            label: None,
+            help: None,
+        }
+    }
+
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Point the diagnostic at `span`, with a short explanation next to the carets.
+    pub fn with_label(mut self, span: Range<usize>, label: &str) -> Self {
+        self.span = Some(span);
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Get the byte range of the offending source.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Get the 1-based line and column of the start of the span.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        let span = self.span.as_ref()?;
+        let start = span.start.min(self.source.len());
+        let before = &self.source[..start];
+
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Some((line, column))
+    }
+
+    fn fmt_help(&self, f: &mut fmt::Formatter<'_>, gutter: &str) -> fmt::Result {
+        if let Some(help) = &self.help {
+            write!(f, "\n{gutter} |\n{gutter} = help: {help}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.level, self.message)?;
+
+        let (Some(span), Some((line, column))) = (&self.span, self.position()) else {
+            write!(f, " --> {}", self.path.display())?;
+            return self.fmt_help(f, " ");
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        writeln!(f, "{gutter}--> {}:{line}:{column}", self.path.display())?;
+        writeln!(f, "{gutter} |")?;
+
+        let text = self.source.lines().nth(line - 1).unwrap_or_default();
+        writeln!(f, "{line} | {text}")?;
+
+        let width = span
+            .end
+            .saturating_sub(span.start)
+            .clamp(1, (text.chars().count() + 1).saturating_sub(column).max(1));
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(width)
+        )?;
+        if let Some(label) = &self.label {
+            write!(f, " {label}")?;
+        }
+
+        self.fmt_help(f, &gutter)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Error => f.write_str("error"),
+            Level::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    const SOURCE: &str = "description = \"Test\"\nversion = 1\n";
+
+    #[gtest]
+    fn test_position() {
+        let path = Path::new("meta.toml");
+        expect_eq!(
+            Diagnostic::new(path, SOURCE, Some(0..1), "").position(),
+            Some((1, 1))
+        );
+        expect_eq!(
+            Diagnostic::new(path, SOURCE, Some(31..32), "").position(),
+            Some((2, 11))
+        );
+        expect_eq!(Diagnostic::new(path, SOURCE, None, "").position(), None);
+    }
+
+    #[test]
+    fn display() {
+        let diagnostic = Diagnostic::new(
+            Path::new("meta.toml"),
+            SOURCE,
+            None,
+            "invalid type: integer `1`, expected a string\n",
+        )
+        .with_label(31..32, "expected a version string")
+        .with_help("versions look like \"1.2.3\"");
+
+        let expected = "\
+error: invalid type: integer `1`, expected a string
+ --> meta.toml:2:11
+  |
+2 | version = 1
+  |           ^ expected a version string
+  |
+  = help: versions look like \"1.2.3\"";
+        assert_eq!(diagnostic.to_string(), expected);
+    }
+
+    #[test]
+    fn display_without_span() {
+        let diagnostic =
+            Diagnostic::new(Path::new("meta.toml"), SOURCE, None, "oops").with_help("try again");
+
+        let expected = "\
+error: oops
+ --> meta.toml
+  |
+  = help: try again";
+        assert_eq!(diagnostic.to_string(), expected);
+    }
+
+    #[test]
+    fn warning() {
+        let diagnostic = Diagnostic::new(Path::new("meta.toml"), SOURCE, Some(0..11), "oops")
+            .with_level(Level::Warning);
+
+        let expected = "\
+warning: oops
+ --> meta.toml:1:1
+  |
+1 | description = \"Test\"
+  | ^^^^^^^^^^^";
+        assert_eq!(diagnostic.to_string(), expected);
+    }
+
+    #[test]
+    fn caret_stops_at_end_of_line() {
+        let diagnostic = Diagnostic::new(Path::new("meta.toml"), SOURCE, Some(21..40), "oops");
+
+        let expected = "\
+error: oops
+ --> meta.toml:2:1
+  |
+2 | version = 1
+  | ^^^^^^^^^^^";
+        assert_eq!(diagnostic.to_string(), expected);
+    }
+}
@@ -3,10 +3,21 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 
-use super::Template;
+use super::{Meta, Template};
 
 impl Template {
     pub fn load(path: &Path) -> Result<Self> {
+        let meta_file = path.join(Self::META_FILE);
+
+        let meta_content = fs::read_to_string(&meta_file)
+            .with_context(|| format!("failed to read meta file: {}", meta_file.display()))?;
+
+        Self::parse(path, &meta_content)
+    }
+
+    /// Load the template at `path` with the given meta file content, such as an unsaved
+    /// editor buffer.
+    pub fn parse(path: &Path, meta_content: &str) -> Result<Self> {
         let name = path
             .file_name()
             .and_then(|s| s.to_str())
@@ -14,17 +25,14 @@ impl Template {
             .to_string();
 
         let meta_file = path.join(Self::META_FILE);
-
-        let meta_content = fs::read_to_string(&meta_file)
-            .with_context(|| format!("failed to read meta file: {}", meta_file.display()))?;
-
-        let meta = toml::from_str(&meta_content)
-            .with_context(|| format!("failed to parse meta file: {}", meta_file.display()))?;
+        let meta = Meta::parse(&meta_file, meta_content)?;
+        let warnings = Meta::lint(&meta_file, meta_content);
 
         Ok(Template {
             name,
             location: path.to_path_buf(),
             meta,
+            warnings,
         })
     }
 }
@@ -64,6 +72,25 @@ mod tests {
         assert_that!(Template::load(home.dirs()[0].path()), err(anything()));
     }
 
+    #[test]
+    fn meta_file_invalid_version() {
+        let home = TemplateHome::single(
+            "test template",
+            Some("description = \"Test\"\nversion = \"1.0\""),
+        );
+        assert_that!(Template::load(home.dirs()[0].path()), err(anything()));
+    }
+
+    #[gtest]
+    fn valid_version() {
+        let home = TemplateHome::single(
+            "test template",
+            Some("description = \"Test\"\nversion = \"1.2.3\""),
+        );
+        let template = Template::load(home.dirs()[0].path()).unwrap();
+        expect_eq!(template.version(), Some(&semver::Version::new(1, 2, 3)));
+    }
+
     #[gtest]
     fn valid_template() {
         let home = TemplateHome::single("test template", Some(r#"description = "Test""#));
@@ -73,5 +100,19 @@ mod tests {
         expect_eq!(template.name(), dir.name());
         expect_eq!(template.description(), "Test");
         expect_eq!(template.location(), dir.path());
+        expect_eq!(template.version(), None);
+        expect_that!(template.warnings(), is_empty());
+    }
+
+    #[gtest]
+    fn unknown_keys() {
+        let home = TemplateHome::single(
+            "test template",
+            Some("description = \"Test\"\nauthor = \"me\"\n"),
+        );
+        let template = Template::load(home.dirs()[0].path()).unwrap();
+
+        assert_that!(template.warnings(), len(eq(1)));
+        expect_eq!(template.warnings()[0].message(), "unknown key `author`");
     }
 }
@@ -1,6 +1,317 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use schemars::{JsonSchema, Schema};
+use semver::Version;
 use serde::{Deserialize, Serialize};
+use toml::Spanned;
+
+use super::{Diagnostic, Level};
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Metadata of a template, read from meta.toml.
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[schemars(title = "tempro template metadata", deny_unknown_fields)]
 pub struct Meta {
+    /// Version of the meta.toml format, the newest one when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "u32", range(min = 1, max = Meta::SCHEMA_VERSION))]
+    pub schema_version: Option<u32>,
+    /// A short summary of the template.
     pub description: String,
+    /// The semver version of the template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Version")]
+    pub version: Option<Version>,
+    /// What applying the template produces.
+    #[serde(default)]
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// A whole project, created as a new directory.
+    #[default]
+    Project,
+    /// A single file, written to stdout or appended to an existing file.
+    Snippet,
+}
+
+impl Meta {
+    /// Keys understood in meta.toml.
+    pub const KEYS: [&'static str; 4] = ["schema_version", "description", "version", "kind"];
+
+    /// The newest meta.toml format this version of tempro understands.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Parse meta.toml, reporting errors as a [`Diagnostic`].
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        let error = match toml::from_str::<Self>(content) {
+            Ok(meta) => return meta.check_schema_version(path, content),
+            Err(error) => error,
+        };
+
+        let diagnostic = Diagnostic::new(path, content, error.span(), error.message());
+
+        let Some(missing) = missing_key(error.message()) else {
+            return Err(diagnostic.into());
+        };
+
+        // A missing key is most likely a misspelled one.
+        let misspelled = unknown_keys(content)
+            .into_iter()
+            .find(|key| suggest_key(key.get_ref()) == Some(missing));
+
+        let diagnostic = match misspelled {
+            Some(key) => diagnostic
+                .with_label(key.span(), "unknown key")
+                .with_help(&format!("did you mean `{missing}`?")),
+            None => diagnostic.with_help(&format!("add a `{missing}` key")),
+        };
+        Err(diagnostic.into())
+    }
+
+    /// Get the JSON Schema of meta.toml.
+    ///
+    /// TOML has no null, so optional keys are described by their value and left out of
+    /// `required`.
+    pub fn schema() -> Schema {
+        schemars::schema_for!(Meta)
+    }
+
+    /// Report keys that are not understood, which would otherwise be ignored.
+    pub fn lint(path: &Path, content: &str) -> Vec<Diagnostic> {
+        unknown_keys(content)
+            .into_iter()
+            .map(|key| {
+                let message = format!("unknown key `{}`", key.get_ref());
+                let help = match suggest_key(key.get_ref()) {
+                    Some(known) => format!("did you mean `{known}`?"),
+                    None => format!("expected one of `{}`", Self::KEYS.join("`, `")),
+                };
+                Diagnostic::new(path, content, None, &message)
+                    .with_level(Level::Warning)
+                    .with_label(key.span(), "unknown key")
+                    .with_help(&help)
+            })
+            .collect()
+    }
+
+    fn check_schema_version(self, path: &Path, content: &str) -> Result<Self> {
+        let schema_version = self.schema_version.unwrap_or(Self::SCHEMA_VERSION);
+        if schema_version == 0 || schema_version > Self::SCHEMA_VERSION {
+            let span = keys(content)
+                .into_iter()
+                .find(|key| key.get_ref() == "schema_version")
+                .map(|key| key.span());
+            let message = format!("unsupported schema version {schema_version}");
+            let help = format!(
+                "this version of tempro supports schema version {}, try upgrading tempro",
+                Self::SCHEMA_VERSION
+            );
+            return Err(Diagnostic::new(path, content, span, &message)
+                .with_help(&help)
+                .into());
+        }
+
+        Ok(self)
+    }
+}
+
+/// Get the closest known key to a misspelled `key`.
+pub fn suggest_key(key: &str) -> Option<&'static str> {
+    Meta::KEYS
+        .into_iter()
+        .map(|known| (known, strsim::jaro_winkler(key, known)))
+        .filter(|(_, similarity)| *similarity > 0.8)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(known, _)| known)
+}
+
+fn missing_key(message: &str) -> Option<&'static str> {
+    let key = message.strip_prefix("missing field `")?.strip_suffix('`')?;
+    Meta::KEYS.into_iter().find(|known| *known == key)
+}
+
+fn keys(content: &str) -> Vec<Spanned<String>> {
+    toml::from_str::<BTreeMap<Spanned<String>, toml::Value>>(content)
+        .map(|table| table.into_keys().collect())
+        .unwrap_or_default()
+}
+
+fn unknown_keys(content: &str) -> Vec<Spanned<String>> {
+    let mut keys = keys(content);
+    keys.retain(|key| !Meta::KEYS.contains(&key.get_ref().as_str()));
+    // Report keys in the order they appear in the file.
+    keys.sort_by_key(|key| key.span().start);
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    fn parse(content: &str) -> String {
+        Meta::parse(Path::new("meta.toml"), content)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn misspelled_key() {
+        let expected = "\
+error: missing field `description`
+ --> meta.toml:2:1
+  |
+2 | descripton = \"Test\"
+  | ^^^^^^^^^^ unknown key
+  |
+  = help: did you mean `description`?";
+        assert_eq!(
+            parse("version = \"1.0.0\"\ndescripton = \"Test\"\n"),
+            expected
+        );
+    }
+
+    #[gtest]
+    fn missing_key() {
+        let diagnostic = parse("version = \"1.0.0\"\n");
+        expect_that!(
+            diagnostic,
+            starts_with("error: missing field `description`")
+        );
+        expect_that!(diagnostic, ends_with("= help: add a `description` key"));
+    }
+
+    #[test]
+    fn invalid_value() {
+        let expected = "\
+error: unexpected end of input while parsing major version number
+ --> meta.toml:2:11
+  |
+2 | version = \"1\"
+  |           ^^^";
+        assert_eq!(parse("description = \"Test\"\nversion = \"1\"\n"), expected);
+    }
+
+    #[gtest]
+    fn schema_version() {
+        let path = Path::new("meta.toml");
+        let meta = Meta::parse(path, "schema_version = 1\ndescription = \"Test\"\n").unwrap();
+        expect_eq!(meta.schema_version, Some(1));
+
+        let diagnostic = parse("schema_version = 2\ndescription = \"Test\"\n");
+        expect_that!(
+            diagnostic,
+            starts_with("error: unsupported schema version 2\n --> meta.toml:1:1")
+        );
+        expect_that!(
+            parse("schema_version = 0\ndescription = \"Test\"\n"),
+            starts_with("error: unsupported schema version 0")
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let content = "\
+author = \"me\"
+description = \"Test\"
+verson = \"1.0.0\"
+
+[varibles]
+name = \"x\"
+";
+        let diagnostics = Meta::lint(Path::new("meta.toml"), content);
+
+        let rendered: Vec<_> = diagnostics.iter().map(ToString::to_string).collect();
+        let expected = [
+            "\
+warning: unknown key `author`
+ --> meta.toml:1:1
+  |
+1 | author = \"me\"
+  | ^^^^^^ unknown key
+  |
+  = help: expected one of `schema_version`, `description`, `version`, `kind`",
+            "\
+warning: unknown key `verson`
+ --> meta.toml:3:1
+  |
+3 | verson = \"1.0.0\"
+  | ^^^^^^ unknown key
+  |
+  = help: did you mean `version`?",
+            "\
+warning: unknown key `varibles`
+ --> meta.toml:5:2
+  |
+5 | [varibles]
+  |  ^^^^^^^^ unknown key
+  |
+  = help: expected one of `schema_version`, `description`, `version`, `kind`",
+        ];
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn lint_known_keys() {
+        let content =
+            "schema_version = 1\ndescription = \"Test\"\nversion = \"1.0.0\"\nkind = \"snippet\"\n";
+        assert!(Meta::lint(Path::new("meta.toml"), content).is_empty());
+    }
+
+    #[gtest]
+    fn kind() {
+        let path = Path::new("meta.toml");
+        expect_eq!(
+            Meta::parse(path, "description = \"Test\"").unwrap().kind,
+            Kind::Project
+        );
+        expect_eq!(
+            Meta::parse(path, "description = \"Test\"\nkind = \"snippet\"")
+                .unwrap()
+                .kind,
+            Kind::Snippet
+        );
+        expect_that!(
+            parse("description = \"Test\"\nkind = \"file\""),
+            starts_with("error: unknown variant `file`, expected `project` or `snippet`")
+        );
+    }
+
+    #[gtest]
+    fn schema_covers_keys() {
+        let schema = Meta::schema();
+        let properties = schema.get("properties").unwrap().as_object().unwrap();
+
+        let keys: Vec<_> = properties.keys().map(String::as_str).collect();
+        expect_that!(
+            keys,
+            unordered_elements_are![
+                eq(&"schema_version"),
+                eq(&"description"),
+                eq(&"version"),
+                eq(&"kind")
+            ]
+        );
+        expect_eq!(
+            schema.get("required").unwrap(),
+            &serde_json::json!(["description"])
+        );
+        expect_eq!(
+            schema.get("additionalProperties"),
+            Some(&serde_json::json!(false))
+        );
+    }
+
+    #[gtest]
+    fn test_suggest_key() {
+        expect_eq!(suggest_key("descripton"), Some("description"));
+        expect_eq!(suggest_key("Description"), Some("description"));
+        expect_eq!(suggest_key("verison"), Some("version"));
+        expect_eq!(suggest_key("author"), None);
+    }
 }
@@ -1,21 +1,40 @@
 pub mod apply;
+mod diagnostic;
 pub mod load;
 mod meta;
+mod origin;
+mod source;
+pub mod verify;
 
 use std::path::{Path, PathBuf};
 
 use meta::Meta;
+use semver::Version;
+
+pub use diagnostic::{Diagnostic, Level};
+pub use meta::Kind;
+pub use origin::Origin;
+pub use source::{LoadedTemplate, TemplateSource};
 
 #[derive(Debug)]
 pub struct Template {
     name: String,
     location: PathBuf,
     meta: Meta,
+    warnings: Vec<Diagnostic>,
 }
 
 impl Template {
     pub const META_FILE: &'static str = "meta.toml";
     pub const TEMPLATE_DIR: &'static str = "template";
+    pub const TESTS_DIR: &'static str = "tests";
+    /// The newest meta.toml format this version of tempro understands.
+    pub const SCHEMA_VERSION: u32 = Meta::SCHEMA_VERSION;
+
+    /// Get the JSON Schema of meta.toml, for editors to validate and complete it.
+    pub fn meta_schema() -> schemars::Schema {
+        Meta::schema()
+    }
 
     pub fn name(&self) -> &str {
         &self.name
@@ -25,7 +44,30 @@ impl Template {
         &self.meta.description
     }
 
+    pub fn version(&self) -> Option<&Version> {
+        self.meta.version.as_ref()
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.meta.kind
+    }
+
     pub fn location(&self) -> &Path {
         &self.location
     }
+
+    /// Problems that do not prevent the template from loading, such as unknown keys.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    /// Get the [`warnings`](Self::warnings) as they are reported,
+    /// which are errors that fail the template if `strict`.
+    pub fn diagnostics(&self, strict: bool) -> Vec<Diagnostic> {
+        let level = if strict { Level::Error } else { Level::Warning };
+        self.warnings
+            .iter()
+            .map(|warning| warning.clone().with_level(level))
+            .collect()
+    }
 }
@@ -0,0 +1,69 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where an installed template comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Origin {
+    /// The git URL or path the template was cloned from.
+    pub source: String,
+    /// The branch or tag requested when installing, if any.
+    pub rev: Option<String>,
+    /// The commit the template is checked out at.
+    pub commit: String,
+}
+
+impl Origin {
+    pub const FILE: &'static str = ".tempro-origin.toml";
+
+    /// Load the origin recorded in a template directory.
+    /// Returns `None` if the template was not installed from a source.
+    pub fn load(template_path: &Path) -> Result<Option<Self>> {
+        let path = template_path.join(Self::FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read origin file: {}", path.display()))?;
+        let origin = toml::from_str(&content)
+            .with_context(|| format!("failed to parse origin file: {}", path.display()))?;
+
+        Ok(Some(origin))
+    }
+
+    pub fn save(&self, template_path: &Path) -> Result<()> {
+        let path = template_path.join(Self::FILE);
+        let content = toml::to_string(self).context("failed to serialize origin")?;
+        fs::write(&path, content)
+            .with_context(|| format!("failed to write origin file: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::prelude::*;
+
+    #[test]
+    fn missing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert_that!(Origin::load(temp_dir.path()), ok(none()));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let origin = Origin {
+            source: "file:///some/repo.git".to_string(),
+            rev: Some("v1.0".to_string()),
+            commit: "0123456789abcdef".to_string(),
+        };
+
+        origin.save(temp_dir.path()).unwrap();
+        assert_eq!(Origin::load(temp_dir.path()).unwrap(), Some(origin));
+    }
+}
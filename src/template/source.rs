@@ -0,0 +1,172 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use tempfile::TempDir;
+
+use super::Template;
+use crate::archive::{self, ArchiveFormat};
+use crate::file;
+
+/// Where a template is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// A template directory, either found in the template roots or given by path.
+    Dir(PathBuf),
+    /// A template archive, unpacked into a temporary directory when loaded.
+    Archive(PathBuf),
+}
+
+impl TemplateSource {
+    /// Resolve a template given by name or by path.
+    /// Valid names are looked up in the template roots first,
+    /// and only when no root has the template is the argument taken as the path
+    /// of a template directory or archive.
+    pub fn resolve(roots: &[PathBuf], name: &str) -> Result<Self> {
+        if file::validate_template_name(name).is_ok()
+            && let Some(entry) = file::lookup_template(roots, name)?
+        {
+            return Ok(Self::Dir(entry.path()));
+        }
+
+        let path = Path::new(name);
+        if path.is_file() && ArchiveFormat::from_path(path).is_some() {
+            return Ok(Self::Archive(path.to_path_buf()));
+        }
+        if path.join(Template::META_FILE).exists() {
+            return Ok(Self::Dir(path.to_path_buf()));
+        }
+
+        bail!("template not found: {name}")
+    }
+
+    pub fn load(&self) -> Result<LoadedTemplate> {
+        match self {
+            Self::Dir(path) => Ok(LoadedTemplate {
+                template: Template::load(path)?,
+                _temp_dir: None,
+            }),
+            Self::Archive(path) => {
+                let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+                // unpack into a directory named after the archive, which becomes the template name
+                let name = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .and_then(ArchiveFormat::strip_extension)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or("template");
+                let dest = temp_dir.path().join(name);
+
+                archive::unpack(path, &dest)?;
+
+                Ok(LoadedTemplate {
+                    template: Template::load(&dest)?,
+                    _temp_dir: Some(temp_dir),
+                })
+            }
+        }
+    }
+}
+
+/// A loaded template, keeping the files of an unpacked archive alive.
+#[derive(Debug)]
+pub struct LoadedTemplate {
+    template: Template,
+    _temp_dir: Option<TempDir>,
+}
+
+impl Deref for LoadedTemplate {
+    type Target = Template;
+
+    fn deref(&self) -> &Template {
+        &self.template
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup() -> (TemplateHome, tempfile::TempDir) {
+        let home = TemplateHome::single("rust/cli", Some(r#"description = "Rust CLI""#));
+        let path = home.dirs()[0].path();
+        fs::create_dir(path.join(Template::TEMPLATE_DIR)).unwrap();
+        fs::write(path.join("template/file.txt"), "Some content").unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        archive::pack(path, &temp_dir.path().join("rust-cli.tar.zst")).unwrap();
+
+        (home, temp_dir)
+    }
+
+    #[gtest]
+    fn resolve_name() {
+        let (home, _temp_dir) = setup();
+        let roots = [home.path().to_path_buf()];
+
+        expect_that!(
+            TemplateSource::resolve(&roots, "rust/cli"),
+            ok(eq(&TemplateSource::Dir(home.path().join("rust/cli"))))
+        );
+    }
+
+    #[gtest]
+    fn resolve_path() {
+        let (home, temp_dir) = setup();
+        let archive = temp_dir.path().join("rust-cli.tar.zst");
+        let dir = home.path().join("rust/cli");
+
+        expect_that!(
+            TemplateSource::resolve(&[], archive.to_str().unwrap()),
+            ok(eq(&TemplateSource::Archive(archive.clone())))
+        );
+        expect_that!(
+            TemplateSource::resolve(&[], dir.to_str().unwrap()),
+            ok(eq(&TemplateSource::Dir(dir.clone())))
+        );
+        expect_that!(
+            TemplateSource::resolve(&[], home.path().to_str().unwrap()),
+            err(anything())
+        );
+    }
+
+    #[test]
+    fn resolve_not_found() {
+        let (home, _temp_dir) = setup();
+        let roots = [home.path().to_path_buf()];
+        assert_that!(TemplateSource::resolve(&roots, "missing"), err(anything()));
+    }
+
+    #[gtest]
+    fn resolve_invalid_version_requirement() {
+        let (home, _temp_dir) = setup();
+        let roots = [home.path().to_path_buf()];
+
+        expect_that!(
+            TemplateSource::resolve(&roots, "rust/cli@not-a-version").map_err(|e| format!("{e:#}")),
+            err(contains_substring("invalid version requirement"))
+        );
+    }
+
+    #[gtest]
+    fn load_archive() {
+        let (_home, temp_dir) = setup();
+        let archive = temp_dir.path().join("rust-cli.tar.zst");
+
+        let template = TemplateSource::Archive(archive).load().unwrap();
+        let location = template.location().to_path_buf();
+
+        expect_eq!(template.name(), "rust-cli");
+        expect_eq!(template.description(), "Rust CLI");
+        expect_that!(location.join("template/file.txt"), file("Some content"));
+
+        drop(template);
+        expect_that!(location.exists(), eq(false));
+    }
+}
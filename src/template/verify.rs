@@ -0,0 +1,261 @@
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use super::{Kind, Template};
+
+/// File names reserved by Windows, regardless of extension.
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+impl Template {
+    /// Verify that the template tree can be applied on any platform.
+    pub fn verify(&self) -> Result<()> {
+        let template_dir = self.location().join(Self::TEMPLATE_DIR);
+        if !template_dir.is_dir() {
+            bail!(
+                "template directory {} is missing or not a directory",
+                template_dir.display()
+            );
+        }
+
+        let root = template_dir
+            .canonicalize()
+            .with_context(|| format!("failed to resolve path: {}", template_dir.display()))?;
+
+        let mut problems = Vec::new();
+        verify_dir(&root, &root, &mut problems)?;
+
+        if !problems.is_empty() {
+            bail!("invalid template tree: {}", problems.join("; "));
+        }
+
+        if self.kind() == Kind::Snippet {
+            self.snippet_file()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn verify_dir(root: &Path, dir: &Path, problems: &mut Vec<String>) -> Result<()> {
+    let entries = match dir.read_dir() {
+        Ok(entries) => entries,
+        Err(e) => {
+            problems.push(format!(
+                "{}: cannot read directory: {e}",
+                relative(root, dir)
+            ));
+            return Ok(());
+        }
+    };
+
+    for entry in entries {
+        let entry = entry.with_context(|| "failed to read a directory entry")?;
+        let path = entry.path();
+        let name = relative(root, &path);
+
+        if let Err(reason) = validate_file_name(&entry.file_name().to_string_lossy()) {
+            problems.push(format!("{name}: {reason}"));
+        }
+
+        let file_type = entry
+            .file_type()
+            .with_context(|| format!("failed to read file type: {}", path.display()))?;
+
+        if file_type.is_symlink() {
+            match path.canonicalize() {
+                Ok(target) if !target.starts_with(root) => {
+                    problems.push(format!("{name}: symlink points outside the template"));
+                }
+                Ok(target) if path.starts_with(&target) => {
+                    problems.push(format!("{name}: symlink points to its own parent"));
+                }
+                Ok(_) => {}
+                Err(_) => problems.push(format!("{name}: broken symlink")),
+            }
+        } else if file_type.is_dir() {
+            verify_dir(root, &path, problems)?;
+        } else if file_type.is_file() {
+            if let Err(e) = File::open(&path) {
+                problems.push(format!("{name}: cannot read file: {e}"));
+            }
+        } else {
+            problems.push(format!("{name}: unsupported file type"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a file name is valid on all supported platforms.
+fn validate_file_name(name: &str) -> Result<(), String> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_control() || matches!(c, '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+    {
+        return Err(format!("file name contains invalid character {c:?}"));
+    }
+
+    if name.ends_with(['.', ' ']) {
+        return Err("file name ends with a dot or a space".to_string());
+    }
+
+    let stem = name.split('.').next().unwrap_or_default();
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return Err(format!("file name {stem} is reserved on Windows"));
+    }
+
+    Ok(())
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    let path = path.strip_prefix(root).unwrap_or(path);
+    Path::new(Template::TEMPLATE_DIR)
+        .join(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::path::PathBuf;
+
+    use crate::test_utils::TemplateHome;
+    use crate::test_utils::prelude::*;
+
+    fn setup() -> (TemplateHome, PathBuf) {
+        let home = TemplateHome::single("test template", Some(r#"description = "Test""#));
+        let template_dir = home.dirs()[0].path().join(Template::TEMPLATE_DIR);
+        fs::create_dir_all(template_dir.join("dir")).unwrap();
+        fs::write(template_dir.join("file.txt"), "Some content").unwrap();
+        fs::write(template_dir.join("dir/file.txt"), "Some content").unwrap();
+        (home, template_dir)
+    }
+
+    fn verify(home: &TemplateHome) -> anyhow::Result<()> {
+        Template::load(home.dirs()[0].path()).unwrap().verify()
+    }
+
+    #[gtest]
+    fn it_works() {
+        let (home, template_dir) = setup();
+        symlink("file.txt", template_dir.join("link")).unwrap();
+        symlink("../dir", template_dir.join("dir/self")).unwrap();
+
+        expect_that!(verify(&home), err(anything()));
+
+        fs::remove_file(template_dir.join("dir/self")).unwrap();
+        expect_that!(verify(&home), ok(()));
+    }
+
+    #[test]
+    fn template_dir_missing() {
+        let home = TemplateHome::single("test template", Some(r#"description = "Test""#));
+        assert_that!(verify(&home), err(anything()));
+    }
+
+    #[test]
+    fn template_dir_is_file() {
+        let home = TemplateHome::single("test template", Some(r#"description = "Test""#));
+        fs::write(home.dirs()[0].path().join(Template::TEMPLATE_DIR), "").unwrap();
+        assert_that!(verify(&home), err(anything()));
+    }
+
+    #[gtest]
+    fn symlink_outside_template() {
+        let (home, template_dir) = setup();
+        symlink("../meta.toml", template_dir.join("meta")).unwrap();
+
+        let error = verify(&home).unwrap_err().to_string();
+        expect_that!(
+            error,
+            contains_substring("template/meta: symlink points outside")
+        );
+    }
+
+    #[gtest]
+    fn broken_symlink() {
+        let (home, template_dir) = setup();
+        symlink("missing", template_dir.join("dir/link")).unwrap();
+
+        let error = verify(&home).unwrap_err().to_string();
+        expect_that!(
+            error,
+            contains_substring("template/dir/link: broken symlink")
+        );
+    }
+
+    #[gtest]
+    fn unsupported_file_type() {
+        let (home, template_dir) = setup();
+        let socket = template_dir.join("socket");
+        let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+
+        let error = verify(&home).unwrap_err().to_string();
+        expect_that!(
+            error,
+            contains_substring("template/socket: unsupported file type")
+        );
+    }
+
+    #[gtest]
+    fn reports_all_problems() {
+        let (home, template_dir) = setup();
+        fs::write(template_dir.join("a:b"), "").unwrap();
+        fs::write(template_dir.join("dir/nul.txt"), "").unwrap();
+
+        let error = verify(&home).unwrap_err().to_string();
+        expect_that!(error, contains_substring("template/a:b"));
+        expect_that!(error, contains_substring("template/dir/nul.txt"));
+    }
+
+    #[test]
+    fn snippet() {
+        let home = TemplateHome::single(
+            "test template",
+            Some("description = \"Test\"\nkind = \"snippet\""),
+        );
+        let template_dir = home.dirs()[0].create_template_dir();
+        fs::write(template_dir.join("snippet.txt"), "snippet").unwrap();
+        assert_that!(verify(&home), ok(()));
+
+        fs::write(template_dir.join("other.txt"), "other").unwrap();
+        assert_that!(verify(&home), err(anything()));
+    }
+
+    #[gtest]
+    fn test_validate_file_name() {
+        for name in [
+            "file.txt",
+            ".gitignore",
+            "Cargo.toml",
+            "console.log",
+            "con-fig",
+        ] {
+            expect_that!(validate_file_name(name), ok(()), "{name}");
+        }
+        for name in [
+            "a:b",
+            "a\\b",
+            "what?",
+            "tab\t",
+            "trailing.",
+            "trailing ",
+            "CON",
+            "aux.rs",
+        ] {
+            expect_that!(validate_file_name(name), err(anything()), "{name}");
+        }
+    }
+}
@@ -1,9 +1,11 @@
 mod matchers;
 mod template_home;
+mod template_repo;
 
 pub mod temp_wd;
 
 pub use template_home::TemplateHome;
+pub use template_repo::TemplateRepo;
 
 pub mod prelude {
     pub use googletest::prelude::*;
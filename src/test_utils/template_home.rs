@@ -46,7 +46,7 @@ pub struct TemplateDir {
 impl TemplateDir {
     fn new(home: &Path, name: &str, content: Option<&str>) -> Self {
         let template_dir = home.join(name);
-        fs::create_dir(&template_dir).unwrap_or_else(|err| {
+        fs::create_dir_all(&template_dir).unwrap_or_else(|err| {
             panic!(
                 "failed to create template dir {}: {err}",
                 template_dir.display()
@@ -73,4 +73,16 @@ impl TemplateDir {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Create an empty template tree, as required by a deep check.
+    pub fn create_template_dir(&self) -> PathBuf {
+        let template_dir = self.path.join(Template::TEMPLATE_DIR);
+        fs::create_dir_all(&template_dir).unwrap_or_else(|err| {
+            panic!(
+                "failed to create template dir {}: {err}",
+                template_dir.display()
+            )
+        });
+        template_dir
+    }
 }
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::template::Template;
+
+/// A git repository containing a single template, used as a remote in tests.
+#[derive(Debug)]
+pub struct TemplateRepo {
+    dir: TempDir,
+}
+
+impl TemplateRepo {
+    pub fn new(meta: &str) -> Self {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let repo = Self { dir };
+
+        repo.git(&["init", "--quiet", "--initial-branch", "main"]);
+        repo.write(Template::META_FILE, meta);
+        repo.write("template/file.txt", "initial content");
+        repo.commit("initial commit");
+
+        repo
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn url(&self) -> String {
+        format!("file://{}", self.path().display())
+    }
+
+    pub fn write(&self, file: &str, content: &str) {
+        let path = self.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content)
+            .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+    }
+
+    pub fn commit(&self, message: &str) {
+        self.git(&["add", "--all"]);
+        self.git(&["commit", "--quiet", "--message", message]);
+    }
+
+    pub fn tag(&self, name: &str) {
+        self.git(&["tag", name]);
+    }
+
+    /// Create a bare clone of the repository in `dest`.
+    pub fn clone_bare(&self, dest: &Path) {
+        self.git(&["clone", "--quiet", "--bare", ".", dest.to_str().unwrap()]);
+    }
+
+    pub fn head(&self) -> String {
+        self.git(&["rev-parse", "HEAD"])
+    }
+
+    pub fn git(&self, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(self.path())
+            .args([
+                "-c",
+                "user.name=tempro",
+                "-c",
+                "user.email=tempro@example.com",
+            ])
+            .args(args)
+            .output()
+            .expect("failed to run git");
+
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+}